@@ -1,40 +1,98 @@
-use crate::tools::{self, InstallMethod, Tool};
+use crate::{
+    cli::ToolSelection,
+    config::Config,
+    history::{Action, Operation},
+    http,
+    registry::Registries,
+    tools::{self, Detection, InstallMethod, MethodKind, Tool, ToolVersion},
+    versions::{CacheMode, UpgradePolicy, check_latest_versions, held_back, is_outdated},
+};
 use anyhow::{Context, Result};
 use colored::*;
 use inquire::MultiSelect;
-use std::{
-    fs, io,
-    path::{Path, PathBuf},
-    process::Command,
-};
-
-pub async fn handle_install_command(tool_name: Option<&str>) -> Result<()> {
+use reqwest::header::HeaderMap;
+use std::{fs, io, path::Path, process::Command};
+
+pub async fn handle_install_command(
+    selection: &ToolSelection,
+    via: Option<MethodKind>,
+    yes: bool,
+    config: &Config,
+    detection: &Detection,
+) -> Result<()> {
     let tools = tools::catalog();
+    let installable = |t: &Tool| via.is_none_or(|kind| t.install_method(kind).is_some());
 
-    if let Some(name) = tool_name {
-        let tool = find_tool(&tools, name).with_context(|| {
-            format!(
-                "Tool '{}' not found. Available tools: {}",
-                name,
-                format_available_tools(&tools)
-            )
+    let targets = if selection.is_empty() {
+        let Some(targets) = select_tools_to_install(&tools, via, config, detection, installable)
+        else {
+            return Ok(());
+        };
+        targets
+    } else if !selection.tools.is_empty() {
+        select_tools(&tools, selection, config, |_| true)?
+    } else {
+        let targets = select_tools(&tools, selection, config, |t| {
+            !detection.is_installed(t) && installable(t)
         })?;
-
-        if tool.is_installed() {
-            println!("{} {} is already installed!", "✓".green(), tool.name);
+        if targets.is_empty() {
+            println!("{}", "Nothing to install.".yellow());
+            return Ok(());
+        }
+        if !yes && !confirm_batch("installed", &targets)? {
+            println!("{}", "Installation cancelled.".yellow());
             return Ok(());
         }
+        targets
+    };
 
-        install_tool(tool).await?;
-        return Ok(());
+    let mut report = BatchReport::default();
+    for tool in targets {
+        if detection.is_installed(tool) {
+            println!("{} {} is already installed!", "✓".green(), tool.name);
+            report.skip(tool, "already installed");
+            continue;
+        }
+        let result = match resolve_install_method(tool, via, config) {
+            Ok(method) => {
+                let mut op = Operation::start(Action::Install, tool, method.kind(), detection);
+                let result = install_tool(tool, method, config, &mut op).await;
+                op.finish(tool, &result).await;
+                result
+            }
+            Err(e) => Err(e),
+        };
+        report.record(tool, result);
     }
+    report.finish("install", "installed")
+}
 
-    let mut uninstalled_tools: Vec<&Tool> = tools.iter().filter(|t| !t.is_installed()).collect();
-    let installed_tools: Vec<&Tool> = tools.iter().filter(|t| t.is_installed()).collect();
+/// Asks which uninstalled tools to install. `None` means there's nothing to do.
+fn select_tools_to_install<'a>(
+    tools: &'a [Tool],
+    via: Option<MethodKind>,
+    config: &Config,
+    detection: &Detection,
+    installable: impl Fn(&Tool) -> bool,
+) -> Option<Vec<&'a Tool>> {
+    let visible = tools.iter().filter(|t| !config.is_hidden(&t.name));
+    let mut uninstalled_tools: Vec<&Tool> = visible
+        .clone()
+        .filter(|t| !detection.is_installed(t))
+        .filter(|t| installable(t))
+        .collect();
+    let installed_tools: Vec<&Tool> = visible.filter(|t| detection.is_installed(t)).collect();
 
     if uninstalled_tools.is_empty() {
-        println!("{}", "All tools are already installed! ✓".green());
-        return Ok(());
+        if let Some(kind) = via {
+            println!(
+                "{}",
+                format!("No uninstalled tools can be installed via {}.", kind).yellow()
+            );
+        } else {
+            println!("{}", "All tools are already installed! ✓".green());
+        }
+        return None;
     }
 
     uninstalled_tools.sort_by(|a, b| a.name.cmp(&b.name));
@@ -44,16 +102,13 @@ pub async fn handle_install_command(tool_name: Option<&str>) -> Result<()> {
     let options: Vec<String> = uninstalled_tools
         .iter()
         .map(|t| {
-            format!(
-                "{} ({})",
-                t.name,
-                match &t.install_method {
-                    InstallMethod::Npm(pkg) => format!("npm: {}", pkg),
-                    InstallMethod::Bootstrap(_) => "bootstrap".to_string(),
-                    InstallMethod::Brew(formula) => format!("brew: {}", formula),
-                    InstallMethod::Amp(_) => "amp installer".to_string(),
-                }
-            )
+            let method = resolve_install_method(t, via, config)
+                .map(InstallMethod::describe)
+                .unwrap_or_default();
+            match &t.description {
+                Some(description) => format!("{} ({}) - {}", t.name, method, description),
+                None => format!("{} ({})", t.name, method),
+            }
         })
         .collect();
 
@@ -66,62 +121,96 @@ pub async fn handle_install_command(tool_name: Option<&str>) -> Result<()> {
     }
 
     let selected = MultiSelect::new("Tools:", options)
-        .with_help_message("↑↓ to move, space to select, enter to confirm")
+        .with_help_message(
+            "↑↓ to move, space to select, enter to confirm; `ai-cli-apps info <tool>` for details",
+        )
         .prompt();
 
     match selected {
         Ok(selections) if !selections.is_empty() => {
             println!("\n{}", "Starting installation...".bright_cyan());
-
-            for selection in selections {
-                if let Some(tool) = uninstalled_tools
-                    .iter()
-                    .find(|t| selection.starts_with(&t.name))
-                    && let Err(e) = install_tool(tool).await
-                {
-                    println!("{} Failed to install {}: {}", "✗".red(), tool.name, e);
-                }
-            }
-
-            println!("\n{}", "Installation complete!".green().bold());
+            Some(
+                uninstalled_tools
+                    .into_iter()
+                    .filter(|t| selections.iter().any(|s| s.starts_with(&t.name)))
+                    .collect(),
+            )
+        }
+        Ok(_) => {
+            println!("{}", "No tools selected.".yellow());
+            None
+        }
+        Err(e) => {
+            println!("{} Selection cancelled: {}", "✗".red(), e);
+            None
         }
-        Ok(_) => println!("{}", "No tools selected.".yellow()),
-        Err(e) => println!("{} Selection cancelled: {}", "✗".red(), e),
     }
-
-    Ok(())
 }
 
 pub async fn handle_uninstall_command(
-    tool_name: Option<&str>,
+    selection: &ToolSelection,
     remove_config: bool,
+    yes: bool,
     force: bool,
+    config: &Config,
+    detection: &Detection,
 ) -> Result<()> {
     let tools = tools::catalog();
 
-    if let Some(name) = tool_name {
-        let tool = find_tool(&tools, name).with_context(|| {
-            format!(
-                "Tool '{}' not found. Available tools: {}",
-                name,
-                format_available_tools(&tools)
-            )
-        })?;
-
-        if !tool.is_installed() {
-            println!("{} {} is not installed!", "!".yellow(), tool.name);
+    let targets = if selection.is_empty() {
+        let Some(targets) = select_tools_to_uninstall(&tools, detection) else {
+            return Ok(());
+        };
+        targets
+    } else if !selection.tools.is_empty() {
+        select_tools(&tools, selection, config, |_| true)?
+    } else {
+        let targets = select_tools(&tools, selection, config, |t| detection.is_installed(t))?;
+        if targets.is_empty() {
+            println!("{}", "Nothing to uninstall.".yellow());
+            return Ok(());
+        }
+        if !yes && !force && !confirm_batch("uninstalled", &targets)? {
+            println!("{}", "Uninstallation cancelled.".yellow());
             return Ok(());
         }
+        targets
+    };
 
-        uninstall_tool(tool, remove_config, force).await?;
-        return Ok(());
+    let mut report = BatchReport::default();
+    for tool in targets {
+        if !detection.is_installed(tool) {
+            println!("{} {} is not installed!", "!".yellow(), tool.name);
+            report.skip(tool, "not installed");
+            continue;
+        }
+        let method = match installed_method(tool, detection) {
+            Ok(method) => method,
+            Err(e) => {
+                report.record(tool, Err(e));
+                continue;
+            }
+        };
+        print_installed_method(tool, method);
+        let mut op = Operation::start(Action::Uninstall, tool, method.kind(), detection);
+        let result = uninstall_tool(tool, method, remove_config, force, &mut op).await;
+        op.finish(tool, &result).await;
+        report.record(tool, result);
     }
+    report.finish("uninstall", "uninstalled")
+}
 
-    let mut installed_tools: Vec<&Tool> = tools.iter().filter(|t| t.is_installed()).collect();
+/// Asks which installed tools to uninstall. `None` means there's nothing to do.
+fn select_tools_to_uninstall<'a>(
+    tools: &'a [Tool],
+    detection: &Detection,
+) -> Option<Vec<&'a Tool>> {
+    let mut installed_tools: Vec<&Tool> =
+        tools.iter().filter(|t| detection.is_installed(t)).collect();
 
     if installed_tools.is_empty() {
         println!("{}", "No tools are currently installed.".yellow());
-        return Ok(());
+        return None;
     }
 
     installed_tools.sort_by(|a, b| a.name.cmp(&b.name));
@@ -137,72 +226,157 @@ pub async fn handle_uninstall_command(
     match selected {
         Ok(selections) if !selections.is_empty() => {
             println!("\n{}", "Starting uninstallation...".bright_cyan());
-
-            for selection in selections {
-                if let Some(tool) = installed_tools.iter().find(|t| t.name == selection)
-                    && let Err(e) = uninstall_tool(tool, remove_config, force).await
-                {
-                    println!("{} Failed to uninstall {}: {}", "✗".red(), tool.name, e);
-                }
-            }
-
-            println!("\n{}", "Uninstallation complete!".green().bold());
+            Some(
+                installed_tools
+                    .into_iter()
+                    .filter(|t| selections.contains(&t.name))
+                    .collect(),
+            )
+        }
+        Ok(_) => {
+            println!("{}", "No tools selected.".yellow());
+            None
+        }
+        Err(e) => {
+            println!("{} Selection cancelled: {}", "✗".red(), e);
+            None
         }
-        Ok(_) => println!("{}", "No tools selected.".yellow()),
-        Err(e) => println!("{} Selection cancelled: {}", "✗".red(), e),
     }
+}
 
-    Ok(())
+/// Named tools are upgraded as asked; --all, --policy and --respect-policy only move tools
+/// with a newer release that the policies allow, which needs the latest versions first.
+pub fn compares_versions(
+    selection: &ToolSelection,
+    policy: Option<UpgradePolicy>,
+    respect_policy: bool,
+) -> bool {
+    selection.tools.is_empty() || policy.is_some() || respect_policy
 }
 
-pub async fn handle_upgrade_command(tool_name: Option<&str>) -> Result<()> {
+pub async fn handle_upgrade_command(
+    selection: &ToolSelection,
+    policy: Option<UpgradePolicy>,
+    respect_policy: bool,
+    config: &Config,
+    detection: &Detection,
+    cache_mode: CacheMode,
+) -> Result<()> {
     let tools = tools::catalog();
 
-    let Some(name) = tool_name else {
+    if selection.is_empty() {
         println!(
-            "{} Specify a tool to upgrade, e.g., `ai-cli-apps upgrade amp`.",
+            "{} Specify a tool to upgrade, e.g., `ai-cli-apps upgrade amp`, or use --all.",
             "!".yellow()
         );
         return Ok(());
-    };
-
-    let tool = find_tool(&tools, name).with_context(|| {
-        format!(
-            "Tool '{}' not found. Available tools: {}",
-            name,
-            format_available_tools(&tools)
-        )
-    })?;
+    }
 
-    if !tool.is_installed() {
-        println!(
-            "{} {} is not installed. Run `ai-cli-apps install {}` first.",
-            "!".yellow(),
-            tool.name,
-            name
-        );
+    let targets = select_tools(&tools, selection, config, |t| detection.is_installed(t))?;
+    if targets.is_empty() {
+        println!("{}", "Nothing to upgrade.".yellow());
         return Ok(());
     }
 
-    upgrade_tool(tool).await
+    let compare = compares_versions(selection, policy, respect_policy);
+    let mut versions: Vec<ToolVersion> = targets
+        .iter()
+        .filter_map(|tool| detection.version(tool).cloned())
+        .collect();
+    if compare {
+        check_latest_versions(&mut versions, config, cache_mode).await;
+    }
+
+    let mut report = BatchReport::default();
+    for tool in targets {
+        if !detection.is_installed(tool) {
+            println!(
+                "{} {} is not installed. Run `ai-cli-apps install {}` first.",
+                "!".yellow(),
+                tool.name,
+                tool.binary_name.as_deref().unwrap_or(&tool.name)
+            );
+            report.skip(tool, "not installed");
+            continue;
+        }
+        if let Some(pin) = config.pin(tool) {
+            println!("{} {}: pinned to {}", "-".bright_black(), tool.name, pin);
+            report.skip(tool, format!("pinned to {}", pin));
+            continue;
+        }
+        if compare {
+            let version = versions.iter().find(|v| v.name == tool.name);
+            if let Some(reason) = upgrade_skip_reason(version, policy, config) {
+                println!("{} {}: {}", "-".bright_black(), tool.name, reason);
+                report.skip(tool, reason);
+                continue;
+            }
+        }
+        let method = match installed_method(tool, detection) {
+            Ok(method) => method,
+            Err(e) => {
+                report.record(tool, Err(e));
+                continue;
+            }
+        };
+        print_installed_method(tool, method);
+        let mut op = Operation::start(Action::Upgrade, tool, method.kind(), detection);
+        let result = upgrade_tool(tool, method, config, &mut op).await;
+        op.finish(tool, &result).await;
+        report.record(tool, result);
+    }
+    report.finish("upgrade", "upgraded")
+}
+
+/// Why an installed tool shouldn't be upgraded, if it shouldn't. `policy` overrides the
+/// configured one.
+fn upgrade_skip_reason(
+    version: Option<&ToolVersion>,
+    policy: Option<UpgradePolicy>,
+    config: &Config,
+) -> Option<String> {
+    let Some(version) = version else {
+        return Some("installed version unknown".to_string());
+    };
+    if version.latest.is_none() {
+        return Some("latest version unknown".to_string());
+    }
+    if !is_outdated(version) {
+        return Some("up to date".to_string());
+    }
+    let hold = match policy {
+        Some(policy) => held_back(version, policy, config.min_release_age(&version.name)),
+        None => version.held_back.clone(),
+    };
+    hold.map(|reason| format!("held back: {}", reason))
 }
 
-async fn install_tool(tool: &Tool) -> Result<()> {
+async fn install_tool(
+    tool: &Tool,
+    method: &InstallMethod,
+    config: &Config,
+    op: &mut Operation,
+) -> Result<()> {
     println!("Installing {}...", tool.name.bright_cyan());
+    // npm and Claude Code's bootstrap script take a version wherever they take a channel.
+    let channel = config.pin(tool).or(config.channel(tool));
+    let registries = Registries::resolve(&config.registries);
 
-    match &tool.install_method {
+    match method {
         InstallMethod::Bootstrap(url) => {
-            run_install_script(url, "bootstrap.sh", "bootstrap script").await?;
+            let args = bootstrap_args(tool, channel);
+            let url = config.installer_url(tool).unwrap_or(url);
+            run_install_script(url, "bootstrap.sh", "bootstrap script", &args, op).await?;
             println!("{} {} installed successfully!", "✓".green(), tool.name);
         }
         InstallMethod::Amp(url) => {
-            run_install_script(url, "amp_install.sh", "Amp installer").await?;
+            let url = config.installer_url(tool).unwrap_or(url);
+            run_install_script(url, "amp_install.sh", "Amp installer", &[], op).await?;
             println!("{} {} installed successfully!", "✓".green(), tool.name);
         }
         InstallMethod::Brew(formula) => {
-            let status = Command::new("brew")
-                .args(["install", formula])
-                .status()
+            let status = op
+                .run(registries.brew_command().args(["install", formula]))
                 .context("Failed to run brew install")?;
 
             if status.success() {
@@ -212,9 +386,13 @@ async fn install_tool(tool: &Tool) -> Result<()> {
             }
         }
         InstallMethod::Npm(package) => {
-            let status = Command::new("npm")
-                .args(["install", "-g", package])
-                .status()
+            let status = op
+                .run(
+                    registries
+                        .npm_command()
+                        .args(["install", "-g"])
+                        .arg(npm_package_spec(package, channel)),
+                )
                 .context("Failed to run npm install")?;
 
             if status.success() {
@@ -228,10 +406,16 @@ async fn install_tool(tool: &Tool) -> Result<()> {
     Ok(())
 }
 
-async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result<()> {
+async fn uninstall_tool(
+    tool: &Tool,
+    method: &InstallMethod,
+    remove_config: bool,
+    force: bool,
+    op: &mut Operation,
+) -> Result<()> {
     println!("Uninstalling {}...", tool.name.bright_cyan());
 
-    match &tool.install_method {
+    match method {
         InstallMethod::Bootstrap(_) => {
             let home = std::env::var("HOME").context("HOME environment variable not set")?;
             let binary_name = tool.binary_name.as_deref().unwrap_or(tool.name.as_str());
@@ -241,20 +425,9 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                 .join("bin")
                 .join(binary_name);
 
-            let versions_path = Path::new(&home)
-                .join(".local")
-                .join("share")
-                .join(binary_name)
-                .join("versions");
-            let config_dirs: Vec<_> = if tool.config_dirs.is_empty() {
-                vec![Path::new(&home).join(format!(".{}", binary_name))]
-            } else {
-                tool.config_dirs
-                    .iter()
-                    .map(|dir| Path::new(&home).join(dir))
-                    .collect()
-            };
-            let mut existing_configs: Vec<_> = config_dirs
+            let versions_path = tool.versions_dir()?;
+            let mut existing_configs: Vec<_> = tool
+                .config_paths()?
                 .into_iter()
                 .filter(|path| path.exists())
                 .collect();
@@ -272,6 +445,7 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                     fs::remove_file(&binary_path).with_context(|| {
                         format!("Failed to remove binary {}", binary_path.display())
                     })?;
+                    op.removed(&binary_path);
                     removed_items.push(format!("binary: {}", binary_path.display()));
                 }
             }
@@ -280,6 +454,7 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                 && let Some(parent) = versions_path.parent()
             {
                 fs::remove_dir_all(parent).context("Failed to remove versions directory")?;
+                op.removed(parent);
                 removed_items.push(format!("versions: {}", parent.display()));
             }
 
@@ -315,6 +490,7 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                             fs::remove_dir_all(&path).with_context(|| {
                                 format!("Failed to remove config directory {}", path.display())
                             })?;
+                            op.removed(&path);
                             removed_items.push(format!("config: {}", path.display()));
                         }
                     } else {
@@ -358,25 +534,17 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                 if shim_path.exists() {
                     fs::remove_file(&shim_path)
                         .with_context(|| format!("Failed to remove {}", shim_path.display()))?;
+                    op.removed(&shim_path);
                     removed_items.push(format!("shim: {}", shim_path.display()));
                 }
             }
 
             if amp_home.exists() {
                 fs::remove_dir_all(&amp_home).context("Failed to remove AMP_HOME directory")?;
+                op.removed(&amp_home);
                 removed_items.push(format!("AMP_HOME: {}", amp_home.display()));
             }
 
-            let config_home = std::env::var("XDG_CONFIG_HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| home_path.join(".config"));
-            let data_home = std::env::var("XDG_DATA_HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| home_path.join(".local").join("share"));
-            let cache_home = std::env::var("XDG_CACHE_HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| home_path.join(".cache"));
-
             if remove_config {
                 let should_remove = if force {
                     true
@@ -391,11 +559,7 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                 };
 
                 if should_remove {
-                    for path in [
-                        config_home.join("amp"),
-                        data_home.join("amp"),
-                        cache_home.join("amp"),
-                    ] {
+                    for path in tool.config_paths()? {
                         if path.exists() {
                             let metadata = fs::metadata(&path)?;
                             if metadata.is_file() {
@@ -407,6 +571,7 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                                     format!("Failed to remove {}", path.display())
                                 })?;
                             }
+                            op.removed(&path);
                             removed_items.push(format!("config/data/cache: {}", path.display()));
                         }
                     }
@@ -435,9 +600,8 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
             }
         }
         InstallMethod::Npm(package) => {
-            let status = Command::new("npm")
-                .args(["uninstall", "-g", package])
-                .status()
+            let status = op
+                .run(Command::new("npm").args(["uninstall", "-g", package]))
                 .context("Failed to run npm uninstall")?;
 
             if status.success() {
@@ -447,9 +611,8 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
             }
         }
         InstallMethod::Brew(formula) => {
-            let status = Command::new("brew")
-                .args(["uninstall", formula])
-                .status()
+            let status = op
+                .run(Command::new("brew").args(["uninstall", formula]))
                 .context("Failed to run brew uninstall")?;
 
             if status.success() {
@@ -463,15 +626,21 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
     Ok(())
 }
 
-async fn upgrade_tool(tool: &Tool) -> Result<()> {
+async fn upgrade_tool(
+    tool: &Tool,
+    method: &InstallMethod,
+    config: &Config,
+    op: &mut Operation,
+) -> Result<()> {
     println!("Upgrading {}...", tool.name.bright_cyan());
+    let channel = config.channel(tool);
+    let registries = Registries::resolve(&config.registries);
 
-    match &tool.install_method {
+    match method {
         InstallMethod::Amp(_) => {
             println!("{} Running `amp update`...", "→".cyan());
-            let status = Command::new("amp")
-                .arg("update")
-                .status()
+            let status = op
+                .run(Command::new("amp").arg("update"))
                 .context("Failed to run `amp update`")?;
 
             if status.success() {
@@ -483,9 +652,8 @@ async fn upgrade_tool(tool: &Tool) -> Result<()> {
         }
         InstallMethod::Brew(formula) => {
             println!("{} Running `brew upgrade {}`...", "→".cyan(), formula);
-            let status = Command::new("brew")
-                .args(["upgrade", formula])
-                .status()
+            let status = op
+                .run(registries.brew_command().args(["upgrade", formula]))
                 .context("Failed to run brew upgrade")?;
 
             if status.success() {
@@ -496,11 +664,10 @@ async fn upgrade_tool(tool: &Tool) -> Result<()> {
             }
         }
         InstallMethod::Npm(package) => {
-            println!("{} Running `npm install -g {}`...", "→".cyan(), package);
-            let status = Command::new("npm")
-                .args(["install", "-g"])
-                .arg(package)
-                .status()
+            let spec = npm_package_spec(package, channel);
+            println!("{} Running `npm install -g {}`...", "→".cyan(), spec);
+            let status = op
+                .run(registries.npm_command().args(["install", "-g"]).arg(&spec))
                 .context("Failed to run npm install")?;
 
             if status.success() {
@@ -519,9 +686,8 @@ async fn upgrade_tool(tool: &Tool) -> Result<()> {
 
             if is_cursor_agent {
                 println!("{} Running `cursor-agent upgrade`...", "→".cyan());
-                let status = Command::new("cursor-agent")
-                    .arg("upgrade")
-                    .status()
+                let status = op
+                    .run(Command::new("cursor-agent").arg("upgrade"))
                     .context("Failed to run cursor-agent upgrade")?;
 
                 if status.success() {
@@ -531,7 +697,10 @@ async fn upgrade_tool(tool: &Tool) -> Result<()> {
                     anyhow::bail!("cursor-agent upgrade failed");
                 }
             } else {
-                run_install_script(url, "bootstrap_upgrade.sh", "bootstrap script").await?;
+                let args = bootstrap_args(tool, channel);
+                let url = config.installer_url(tool).unwrap_or(url);
+                run_install_script(url, "bootstrap_upgrade.sh", "bootstrap script", &args, op)
+                    .await?;
                 println!("{} {} upgraded successfully!", "✓".green(), tool.name);
                 Ok(())
             }
@@ -539,11 +708,19 @@ async fn upgrade_tool(tool: &Tool) -> Result<()> {
     }
 }
 
-async fn run_install_script(url: &str, temp_filename: &str, description: &str) -> Result<()> {
+async fn run_install_script(
+    url: &str,
+    temp_filename: &str,
+    description: &str,
+    args: &[&str],
+    op: &mut Operation,
+) -> Result<()> {
     println!("{} Downloading {}...", "→".cyan(), description);
 
-    let script = reqwest::get(url)
+    let script = http::client()
+        .get(url, HeaderMap::new())
         .await
+        .and_then(|response| response.error_for_status())
         .with_context(|| format!("Failed to download {}", description))?
         .text()
         .await
@@ -564,9 +741,11 @@ async fn run_install_script(url: &str, temp_filename: &str, description: &str) -
     println!("{} Running {}...", "→".cyan(), description);
     println!();
 
-    let status = Command::new("bash")
-        .arg(&script_path)
-        .status()
+    let status = op
+        .run_as(
+            Command::new("bash").arg(&script_path).args(args),
+            format!("bash <(curl -fsSL {}) {}", url, args.join(" ")),
+        )
         .context("Failed to run install script")?;
 
     let _ = fs::remove_file(&script_path);
@@ -593,12 +772,283 @@ fn format_available_tools(tools: &[Tool]) -> String {
         .join(", ")
 }
 
+/// `package@channel` when a non-default release channel is configured.
+fn npm_package_spec(package: &str, channel: Option<&str>) -> String {
+    match channel {
+        Some(channel) => format!("{}@{}", package, channel),
+        None => package.to_string(),
+    }
+}
+
+fn bootstrap_args<'a>(tool: &Tool, channel: Option<&'a str>) -> Vec<&'a str> {
+    match channel {
+        Some(channel) if tool.bootstrap_channels => vec![channel],
+        _ => Vec::new(),
+    }
+}
+
+/// Tools named on the command line, or for `--all`/`--all-except` every catalog tool
+/// that isn't hidden and is accepted by `eligible`. Unknown names are an error before
+/// anything runs.
+fn select_tools<'a>(
+    tools: &'a [Tool],
+    selection: &ToolSelection,
+    config: &Config,
+    eligible: impl Fn(&Tool) -> bool,
+) -> Result<Vec<&'a Tool>> {
+    let mut selected: Vec<&Tool> = Vec::new();
+    if !selection.tools.is_empty() {
+        for name in &selection.tools {
+            let tool = require_tool(tools, name)?;
+            if !selected.iter().any(|t| t.name == tool.name) {
+                selected.push(tool);
+            }
+        }
+        return Ok(selected);
+    }
+
+    let excluded = selection
+        .all_except
+        .iter()
+        .map(|name| require_tool(tools, name))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(tools
+        .iter()
+        .filter(|t| !excluded.iter().any(|e| e.name == t.name))
+        .filter(|t| !config.is_hidden(&t.name))
+        .filter(|t| eligible(t))
+        .collect())
+}
+
+fn confirm_batch(done: &str, tools: &[&Tool]) -> Result<bool> {
+    println!("The following tools will be {}:", done);
+    for tool in tools {
+        println!("  - {}", tool.name);
+    }
+    println!("{} Continue? [y/N]", "?".yellow());
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+enum Outcome {
+    Done,
+    Skipped(String),
+    Failed(anyhow::Error),
+}
+
+/// Per-tool results of an install or uninstall run, summarised once all tools are done.
+#[derive(Default)]
+struct BatchReport {
+    outcomes: Vec<(String, Outcome)>,
+}
+
+impl BatchReport {
+    fn skip(&mut self, tool: &Tool, reason: impl Into<String>) {
+        self.outcomes
+            .push((tool.name.clone(), Outcome::Skipped(reason.into())));
+    }
+
+    fn record(&mut self, tool: &Tool, result: Result<()>) {
+        let outcome = match result {
+            Ok(()) => Outcome::Done,
+            Err(e) => Outcome::Failed(e),
+        };
+        self.outcomes.push((tool.name.clone(), outcome));
+    }
+
+    /// Prints a summary for multi-tool runs and fails if any tool failed.
+    fn finish(mut self, action: &str, done: &str) -> Result<()> {
+        let total = self.outcomes.len();
+        if total > 1 {
+            let width = self
+                .outcomes
+                .iter()
+                .map(|(name, _)| name.len())
+                .max()
+                .unwrap_or(0);
+            println!("\n{}", "Summary:".bold());
+            for (name, outcome) in &self.outcomes {
+                let (mark, detail) = match outcome {
+                    Outcome::Done => ("✓".green(), done.green().to_string()),
+                    Outcome::Skipped(reason) => {
+                        ("-".bright_black(), reason.bright_black().to_string())
+                    }
+                    Outcome::Failed(e) => ("✗".red(), format!("{:#}", e).red().to_string()),
+                };
+                println!("  {} {:width$}  {}", mark, name, detail, width = width);
+            }
+        }
+
+        let failures = self
+            .outcomes
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, Outcome::Failed(_)))
+            .count();
+        if failures == 0 {
+            return Ok(());
+        }
+        // A single tool's error is more useful than a count.
+        if total == 1
+            && let Some((_, Outcome::Failed(e))) = self.outcomes.pop()
+        {
+            return Err(e);
+        }
+        anyhow::bail!("{} of {} tools failed to {}", failures, total, action)
+    }
+}
+
+/// The tool called `name`, or an error listing the available tools.
+pub(crate) fn require_tool<'a>(tools: &'a [Tool], name: &str) -> Result<&'a Tool> {
+    find_tool(tools, name).with_context(|| {
+        format!(
+            "Tool '{}' not found. Available tools: {}",
+            name,
+            format_available_tools(tools)
+        )
+    })
+}
+
 fn find_tool<'a>(tools: &'a [Tool], name: &str) -> Option<&'a Tool> {
-    tools.iter().find(|t| {
-        t.name.eq_ignore_ascii_case(name)
-            || t.binary_name
-                .as_ref()
-                .map(|b| b.eq_ignore_ascii_case(name))
-                .unwrap_or(false)
+    tools.iter().find(|t| t.matches(name))
+}
+
+fn resolve_install_method<'a>(
+    tool: &'a Tool,
+    via: Option<MethodKind>,
+    config: &Config,
+) -> Result<&'a InstallMethod> {
+    let kind = via.unwrap_or_else(|| config.preferred_install_method(tool));
+    tool.install_method(kind).with_context(|| {
+        format!(
+            "{} cannot be installed via {}. Available methods: {}",
+            tool.name,
+            kind,
+            tool.available_methods()
+        )
     })
 }
+
+/// The method the installed copy came from, so upgrades and removals go through the same
+/// package manager that installed it.
+fn installed_method<'a>(tool: &'a Tool, detection: &Detection) -> Result<&'a InstallMethod> {
+    detection
+        .method(tool)
+        .and_then(|kind| tool.install_method(kind))
+        .with_context(|| {
+            let location = tool
+                .binary_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "its binary isn't on PATH".to_string());
+            format!(
+                "Unknown install method for {} ({}); it supports {}. Upgrade or remove it with whatever installed it.",
+                tool.name,
+                location,
+                tool.available_methods()
+            )
+        })
+}
+
+fn print_installed_method(tool: &Tool, method: &InstallMethod) {
+    if tool.install_methods.len() > 1 {
+        println!("{} Installed via {}", "→".cyan(), method.kind());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchReport, select_tools};
+    use crate::{cli::ToolSelection, config::Config, tools};
+
+    fn names(selected: anyhow::Result<Vec<&tools::Tool>>) -> Vec<String> {
+        selected
+            .unwrap()
+            .iter()
+            .map(|tool| tool.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn it_selects_named_tools_or_every_eligible_visible_tool_except_some() {
+        let catalog = tools::catalog();
+        let config: Config = toml::from_str("[tools.kilo]\nhidden = true\n").unwrap();
+        let selection = |tools: &[&str], all: bool, all_except: &[&str]| ToolSelection {
+            tools: tools.iter().map(|name| name.to_string()).collect(),
+            all,
+            all_except: all_except.iter().map(|name| name.to_string()).collect(),
+        };
+
+        // Named tools keep their order, drop duplicates and may be hidden.
+        assert_eq!(
+            names(select_tools(
+                &catalog,
+                &selection(&["codex", "claude", "codex", "kilo"], false, &[]),
+                &config,
+                |_| false,
+            )),
+            ["Codex CLI", "Claude Code", "Kilo Code CLI"]
+        );
+
+        let starts_with_c = |tool: &tools::Tool| tool.name.starts_with('C');
+        assert_eq!(
+            names(select_tools(
+                &catalog,
+                &selection(&[], false, &["claude", "cline"]),
+                &config,
+                starts_with_c,
+            )),
+            ["Codex CLI", "Cursor CLI", "Copilot CLI"]
+        );
+        assert!(
+            !names(select_tools(
+                &catalog,
+                &selection(&[], true, &[]),
+                &config,
+                |_| true
+            ))
+            .contains(&"Kilo Code CLI".to_string())
+        );
+
+        let unknown = select_tools(&catalog, &selection(&[], false, &["nope"]), &config, |_| {
+            true
+        });
+        assert!(
+            unknown
+                .unwrap_err()
+                .to_string()
+                .contains("Tool 'nope' not found")
+        );
+    }
+
+    #[test]
+    fn it_fails_a_batch_only_when_a_tool_failed() {
+        let catalog = tools::catalog();
+
+        let mut report = BatchReport::default();
+        report.record(&catalog[0], Ok(()));
+        report.skip(&catalog[1], "already installed");
+        assert!(report.finish("install", "installed").is_ok());
+
+        let mut report = BatchReport::default();
+        report.record(&catalog[0], Err(anyhow::anyhow!("npm exited with 1")));
+        assert_eq!(
+            report
+                .finish("install", "installed")
+                .unwrap_err()
+                .to_string(),
+            "npm exited with 1"
+        );
+
+        let mut report = BatchReport::default();
+        report.record(&catalog[0], Err(anyhow::anyhow!("npm exited with 1")));
+        report.record(&catalog[1], Ok(()));
+        report.skip(&catalog[2], "not installed");
+        assert_eq!(
+            report
+                .finish("install", "installed")
+                .unwrap_err()
+                .to_string(),
+            "1 of 3 tools failed to install"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+/// HTTP validators from a previous response, sent back as `If-None-Match` and
+/// `If-Modified-Since` so unchanged upstream data costs a 304 instead of a full download.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub version: String,
+    /// Seconds since the Unix epoch when upstream last confirmed this version.
+    pub fetched_at: u64,
+    /// Seconds since the Unix epoch when `version` was published, if the source says.
+    #[serde(default)]
+    pub published_at: Option<u64>,
+    #[serde(default)]
+    pub validators: Validators,
+}
+
+impl CacheEntry {
+    pub fn new(version: String, published_at: Option<u64>, validators: Validators) -> Self {
+        Self {
+            version,
+            fetched_at: now(),
+            published_at,
+            validators,
+        }
+    }
+
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now().saturating_sub(self.fetched_at))
+    }
+
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        self.age() < ttl
+    }
+
+    pub fn touch(&mut self) {
+        self.fetched_at = now();
+    }
+}
+
+/// Latest-version lookups persisted to `~/.cache/ai-cli-apps/latest.json`.
+#[derive(Debug, Default)]
+pub struct LatestCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl LatestCache {
+    /// Loads the cache, treating a missing or unreadable file as empty.
+    pub fn load() -> Self {
+        let entries = cache_path()
+            .ok()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default();
+        Self { entries }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = cache_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create cache directory {}", parent.display())
+            })?;
+        }
+        let content = serde_json::to_vec_pretty(&self.entries)?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write cache file {}", path.display()))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&CacheEntry> {
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, key: String, entry: CacheEntry) {
+        self.entries.insert(key, entry);
+    }
+}
+
+/// Cache key for a tool's lookup on a given release channel.
+pub fn cache_key(tool_name: &str, channel: &str) -> String {
+    format!("{}@{}", tool_name, channel)
+}
+
+/// Cache key for a lookup answered by the tool's fallback source, kept apart so its validators
+/// are never sent to the primary source.
+pub fn fallback_key(key: &str) -> String {
+    format!("{} (fallback)", key)
+}
+
+pub fn cache_path() -> Result<PathBuf> {
+    Ok(paths::cache_dir()?.join("latest.json"))
+}
+
+/// Short human-readable age such as `45s`, `12m`, `3h` or `2d`.
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,496 @@
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+
+use crate::{
+    actions::require_tool,
+    config::Config,
+    github,
+    registry::Registries,
+    tools::{self, DEFAULT_CHANNEL, Detection},
+    versions::{
+        CacheMode, LookupError, check_latest_versions, fetch_page, fetch_text, is_newer_version,
+    },
+};
+
+/// How many entries to show for a tool that isn't installed.
+const RECENT_RELEASES: usize = 5;
+/// How many pages of GitHub releases to read before giving up on reaching the installed one.
+const MAX_RELEASE_PAGES: usize = 10;
+
+static VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.]+)?").unwrap());
+static LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\([^)]*\)").unwrap());
+
+/// Where a tool publishes release notes.
+#[derive(Debug, Clone, Copy)]
+enum NotesSource {
+    GitHubReleases(&'static str),
+    /// `CHANGELOG.md` shipped in the npm package.
+    NpmChangelog(&'static str),
+    /// Version bumps in the homebrew-core formula's commit history.
+    BrewHistory(&'static str),
+}
+
+/// Sources per tool, tried in order until one has notes.
+const NOTES_SOURCES: &[(&str, &[NotesSource])] = &[
+    (
+        "Claude Code",
+        &[NotesSource::NpmChangelog("@anthropic-ai/claude-code")],
+    ),
+    ("Amp", &[NotesSource::NpmChangelog("@sourcegraph/amp")]),
+    (
+        "Codex CLI",
+        &[
+            NotesSource::GitHubReleases("openai/codex"),
+            NotesSource::BrewHistory("codex"),
+        ],
+    ),
+    (
+        "Copilot CLI",
+        &[
+            NotesSource::GitHubReleases("github/copilot-cli"),
+            NotesSource::NpmChangelog("@github/copilot"),
+        ],
+    ),
+    (
+        "Kilo Code CLI",
+        &[NotesSource::NpmChangelog("@kilocode/cli")],
+    ),
+    (
+        "Gemini CLI",
+        &[
+            NotesSource::GitHubReleases("google-gemini/gemini-cli"),
+            NotesSource::BrewHistory("gemini-cli"),
+        ],
+    ),
+    ("Cline CLI", &[NotesSource::NpmChangelog("cline")]),
+    ("OpenCode", &[NotesSource::GitHubReleases("sst/opencode")]),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+struct ReleaseNotes {
+    version: String,
+    date: Option<String>,
+    body: String,
+}
+
+impl NotesSource {
+    fn describe(self) -> String {
+        match self {
+            NotesSource::GitHubReleases(repo) => format!("GitHub releases of {}", repo),
+            NotesSource::NpmChangelog(package) => format!("CHANGELOG.md of {}", package),
+            NotesSource::BrewHistory(formula) => format!("Homebrew history of {}", formula),
+        }
+    }
+
+    /// Notes newest first, reaching back at least to `installed` where the source pages.
+    async fn fetch(
+        self,
+        registries: &Registries,
+        installed: Option<&str>,
+        latest: Option<&str>,
+        prereleases: bool,
+    ) -> Result<Vec<ReleaseNotes>, LookupError> {
+        match self {
+            NotesSource::GitHubReleases(repo) => {
+                let headers = github::api_headers(registries.github_api());
+                let mut url = Some(format!(
+                    "{}/repos/{}/releases?per_page=100",
+                    registries.github_api(),
+                    repo
+                ));
+                let mut notes = Vec::new();
+                for _ in 0..MAX_RELEASE_PAGES {
+                    let Some(page) = url.take() else {
+                        break;
+                    };
+                    let (body, next_page) = fetch_page(&page, headers.clone()).await?;
+                    notes.extend(parse_github_releases(&page, &body, prereleases)?);
+                    let reached = match installed {
+                        Some(installed) => notes
+                            .iter()
+                            .any(|entry| !is_newer_version(&entry.version, installed)),
+                        None => notes.len() >= RECENT_RELEASES,
+                    };
+                    if !reached {
+                        url = next_page;
+                    }
+                }
+                Ok(notes)
+            }
+            NotesSource::NpmChangelog(package) => {
+                let url =
+                    registries.npm_file_url(package, latest.unwrap_or("latest"), "CHANGELOG.md");
+                let body = fetch_text(&url, HeaderMap::new()).await?;
+                Ok(parse_changelog(&body))
+            }
+            NotesSource::BrewHistory(formula) => {
+                let first = formula.chars().next().unwrap_or('_');
+                let url = format!(
+                    "{}/repos/Homebrew/homebrew-core/commits?path=Formula/{}/{}.rb&per_page=100",
+                    registries.github_api(),
+                    first,
+                    formula
+                );
+                let body = fetch_text(&url, github::api_headers(registries.github_api())).await?;
+                parse_brew_commits(&url, formula, &body)
+            }
+        }
+    }
+}
+
+pub async fn handle_changelog_command(
+    tool_name: &str,
+    config: &Config,
+    detection: &Detection,
+    cache_mode: CacheMode,
+) -> Result<()> {
+    if cache_mode == CacheMode::Offline {
+        anyhow::bail!("Release notes aren't cached; run changelog without --offline");
+    }
+    let catalog = tools::catalog();
+    let tool = require_tool(&catalog, tool_name)?;
+    let Some((_, sources)) = NOTES_SOURCES.iter().find(|(name, _)| *name == tool.name) else {
+        anyhow::bail!("{} doesn't publish release notes we can read", tool.name);
+    };
+
+    let mut versions: Vec<_> = detection.version(tool).cloned().into_iter().collect();
+    check_latest_versions(&mut versions, config, cache_mode).await;
+    let installed = versions.first().and_then(|v| v.installed.as_deref());
+    let latest = versions.first().and_then(|v| v.latest.as_deref());
+    let installed = installed.and_then(extract_version);
+    let prereleases = versions
+        .first()
+        .and_then(|v| v.channel.as_deref())
+        .is_some_and(is_prerelease_channel);
+
+    let registries = Registries::resolve(&config.registries);
+    let mut errors = Vec::new();
+    for source in *sources {
+        let fetched = source
+            .fetch(&registries, installed.as_deref(), latest, prereleases)
+            .await;
+        let notes = match fetched {
+            Ok(notes) if !notes.is_empty() => notes,
+            Ok(_) => continue,
+            Err(e) => {
+                errors.push(format!("{}: {}", source.describe(), e));
+                continue;
+            }
+        };
+
+        print_header(&tool.name, installed.as_deref(), latest, *source);
+        let notes = match &installed {
+            Some(installed) => notes_between(notes, installed, latest),
+            None => notes.into_iter().take(RECENT_RELEASES).collect(),
+        };
+        if notes.is_empty() {
+            println!(
+                "{}",
+                "No release notes newer than the installed version.".green()
+            );
+        }
+        for entry in &notes {
+            print_notes(entry);
+        }
+        return Ok(());
+    }
+
+    if errors.is_empty() {
+        anyhow::bail!("No release notes found for {}", tool.name);
+    }
+    anyhow::bail!(
+        "Could not fetch release notes for {}:\n  {}",
+        tool.name,
+        errors.join("\n  ")
+    )
+}
+
+fn print_header(name: &str, installed: Option<&str>, latest: Option<&str>, source: NotesSource) {
+    let range = match (installed, latest) {
+        (Some(installed), Some(latest)) => format!("{} → {}", installed, latest),
+        (Some(installed), None) => format!("newer than {}", installed),
+        (None, _) => "not installed, showing recent releases".to_string(),
+    };
+    println!(
+        "{} {} {}\n",
+        format!("{}:", name).bold(),
+        range,
+        format!("({})", source.describe()).bright_black()
+    );
+}
+
+fn print_notes(entry: &ReleaseNotes) {
+    let date = entry
+        .date
+        .as_deref()
+        .map(|date| format!("  {}", date).bright_black().to_string())
+        .unwrap_or_default();
+    println!("{}{}", entry.version.bright_blue().bold(), date);
+    for line in entry.body.lines().filter(|line| !line.trim().is_empty()) {
+        println!("{}", render_line(line));
+    }
+    println!();
+}
+
+/// Markdown reduced to something readable in a terminal.
+fn render_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let text = LINK
+        .replace_all(trimmed, "$1")
+        .replace("**", "")
+        .replace('`', "");
+    if let Some(heading) = text.strip_prefix('#') {
+        format!("  {}", heading.trim_start_matches('#').trim().bold())
+    } else if let Some(item) = text.strip_prefix("- ").or_else(|| text.strip_prefix("* ")) {
+        format!("  {}• {}", indent, item)
+    } else {
+        format!("  {}{}", indent, text)
+    }
+}
+
+/// Entries newer than `installed` and, when known, not newer than `latest`.
+fn notes_between(
+    notes: Vec<ReleaseNotes>,
+    installed: &str,
+    latest: Option<&str>,
+) -> Vec<ReleaseNotes> {
+    notes
+        .into_iter()
+        .filter(|entry| is_newer_version(&entry.version, installed))
+        .filter(|entry| latest.is_none_or(|latest| !is_newer_version(&entry.version, latest)))
+        .collect()
+}
+
+/// Channels other than the default and `stable`, such as npm's `next` or `beta`, follow
+/// prereleases.
+fn is_prerelease_channel(channel: &str) -> bool {
+    !matches!(channel, DEFAULT_CHANNEL | "stable")
+}
+
+fn extract_version(text: &str) -> Option<String> {
+    VERSION.find(text).map(|m| m.as_str().to_string())
+}
+
+fn parse_github_releases(
+    url: &str,
+    body: &str,
+    prereleases: bool,
+) -> Result<Vec<ReleaseNotes>, LookupError> {
+    let releases: Vec<github::Release> =
+        serde_json::from_str(body).map_err(|e| LookupError::invalid(url, e))?;
+    Ok(releases
+        .into_iter()
+        .filter(|release| !release.draft && (prereleases || !release.prerelease))
+        .filter_map(|release| {
+            Some(ReleaseNotes {
+                version: extract_version(&release.tag_name)?,
+                date: release
+                    .published_at
+                    .map(|date| date.chars().take(10).collect()),
+                body: release.body.unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+/// Splits a Keep-a-Changelog style file on headings that contain a version.
+fn parse_changelog(markdown: &str) -> Vec<ReleaseNotes> {
+    let mut notes: Vec<ReleaseNotes> = Vec::new();
+    for line in markdown.lines() {
+        let heading = line
+            .starts_with('#')
+            .then(|| extract_version(line))
+            .flatten();
+        match (heading, notes.last_mut()) {
+            (Some(version), _) => notes.push(ReleaseNotes {
+                version,
+                date: None,
+                body: String::new(),
+            }),
+            (None, Some(entry)) => {
+                entry.body.push_str(line);
+                entry.body.push('\n');
+            }
+            (None, None) => {}
+        }
+    }
+    notes
+}
+
+#[derive(Deserialize)]
+struct Commit {
+    commit: CommitDetails,
+}
+
+#[derive(Deserialize)]
+struct CommitDetails {
+    message: String,
+    committer: Option<CommitAuthor>,
+}
+
+#[derive(Deserialize)]
+struct CommitAuthor {
+    date: Option<String>,
+}
+
+/// `formula 1.2.3` commits from homebrew-core, newest first, one per version.
+fn parse_brew_commits(
+    url: &str,
+    formula: &str,
+    body: &str,
+) -> Result<Vec<ReleaseNotes>, LookupError> {
+    let commits: Vec<Commit> =
+        serde_json::from_str(body).map_err(|e| LookupError::invalid(url, e))?;
+    let prefix = format!("{} ", formula);
+    let mut notes: Vec<ReleaseNotes> = Vec::new();
+    for commit in commits {
+        let subject = commit
+            .commit
+            .message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let Some(version) = subject.strip_prefix(&prefix).and_then(extract_version) else {
+            continue;
+        };
+        if subject.contains("bottle") || notes.iter().any(|entry| entry.version == version) {
+            continue;
+        }
+        notes.push(ReleaseNotes {
+            version,
+            date: commit
+                .commit
+                .committer
+                .and_then(|committer| committer.date)
+                .map(|date| date.chars().take(10).collect()),
+            body: subject,
+        });
+    }
+    Ok(notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NotesSource, notes_between, parse_changelog};
+    use crate::{config::RegistryConfig, registry::Registries};
+    use httpmock::prelude::*;
+
+    #[test]
+    fn it_splits_a_changelog_and_keeps_versions_after_the_installed_one() {
+        let notes = parse_changelog(
+            "# Changelog\n\n## 1.0.44\n\n- Faster startup\n\n## [1.0.31] - 2025-06-01\n\n\
+             - Fixed login\n\n## 1.0.30\n\n- Initial\n",
+        );
+        let versions: Vec<_> = notes.iter().map(|n| n.version.as_str()).collect();
+        assert_eq!(versions, ["1.0.44", "1.0.31", "1.0.30"]);
+        assert!(notes[0].body.contains("- Faster startup"));
+
+        let between = notes_between(notes.clone(), "1.0.30", Some("1.0.31"));
+        assert_eq!(between.len(), 1);
+        assert_eq!(between[0].version, "1.0.31");
+        assert_eq!(notes_between(notes, "1.0.30", None).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn it_reads_github_releases_and_brew_history() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/repos/openai/codex/releases");
+                then.status(200).body(
+                    r#"[{"tag_name":"rust-v0.46.0","body":"- New","published_at":"2025-10-10T00:00:00Z"},
+                        {"tag_name":"rust-v0.47.0-alpha.1","body":"","draft":true}]"#,
+                );
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/repos/Homebrew/homebrew-core/commits")
+                    .query_param("path", "Formula/c/codex.rb");
+                then.status(200).body(
+                    r#"[{"commit":{"message":"codex: update 0.46.0 bottle."}},
+                        {"commit":{"message":"codex 0.46.0\n\nCloses #1"}},
+                        {"commit":{"message":"codex 0.45.0","committer":{"date":"2025-10-01T00:00:00Z"}}}]"#,
+                );
+            })
+            .await;
+        let registries = Registries::resolve(&RegistryConfig {
+            github_api: Some(server.base_url()),
+            ..RegistryConfig::default()
+        });
+
+        let releases = NotesSource::GitHubReleases("openai/codex")
+            .fetch(&registries, None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].version, "0.46.0");
+        assert_eq!(releases[0].date.as_deref(), Some("2025-10-10"));
+
+        let history = NotesSource::BrewHistory("codex")
+            .fetch(&registries, None, None, false)
+            .await
+            .unwrap();
+        let versions: Vec<_> = history.iter().map(|n| n.version.as_str()).collect();
+        assert_eq!(versions, ["0.46.0", "0.45.0"]);
+    }
+
+    #[tokio::test]
+    async fn it_pages_through_releases_until_the_installed_one_and_skips_prereleases() {
+        let server = MockServer::start_async().await;
+        let next = format!("{}/repos/sst/opencode/releases?page=2", server.base_url());
+        server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/repos/sst/opencode/releases")
+                    .query_param_missing("page");
+                then.status(200)
+                    .header("link", format!(r#"<{}>; rel="next""#, next))
+                    .body(
+                        r#"[{"tag_name":"v0.16.0-rc.1","prerelease":true},
+                            {"tag_name":"v0.15.3"},{"tag_name":"v0.15.2"}]"#,
+                    );
+            })
+            .await;
+        let second = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/repos/sst/opencode/releases")
+                    .query_param("page", "2");
+                then.status(200)
+                    .body(r#"[{"tag_name":"v0.15.1"},{"tag_name":"v0.15.0"}]"#);
+            })
+            .await;
+        let registries = Registries::resolve(&RegistryConfig {
+            github_api: Some(server.base_url()),
+            ..RegistryConfig::default()
+        });
+        let source = NotesSource::GitHubReleases("sst/opencode");
+        let versions = |notes: Vec<super::ReleaseNotes>| -> Vec<String> {
+            notes.into_iter().map(|n| n.version).collect()
+        };
+
+        let notes = source
+            .fetch(&registries, Some("0.15.0"), None, false)
+            .await
+            .unwrap();
+        assert_eq!(versions(notes), ["0.15.3", "0.15.2", "0.15.1", "0.15.0"]);
+        second.assert_calls_async(1).await;
+
+        let notes = source
+            .fetch(&registries, Some("0.15.2"), None, true)
+            .await
+            .unwrap();
+        assert_eq!(versions(notes), ["0.16.0-rc.1", "0.15.3", "0.15.2"]);
+        second.assert_calls_async(1).await;
+    }
+}
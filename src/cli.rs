@@ -1,69 +1,309 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Command, CommandFactory, Parser, Subcommand};
+
+use crate::{
+    completions::Shell,
+    output::ColorChoice,
+    schedule::Frequency,
+    tools::{self, MethodKind},
+    versions::{FailOn, UpgradePolicy},
+};
 
 #[derive(Parser)]
 #[command(name = "ai-cli-apps")]
 #[command(arg_required_else_help = false)]
 #[command(disable_version_flag = true)]
-#[command(
-    about = "Check and manage AI CLI tools versions",
-    long_about = "Check and manage AI CLI tools versions\n\nSupported tools:\n  Claude Code (claude)\n  Amp (amp)\n  Codex (codex)\n  Cursor (cursor)\n  Copilot CLI (copilot)\n  Kilo (kilo)\n  Gemini (gemini)\n  Cline (cline)\n  OpenCode (opencode)\n  Factory CLI (droid)",
-    version
-)]
+#[command(about = "Check and manage AI CLI tools versions", version)]
 pub struct Cli {
     /// Print version
     #[arg(short = 'v', long, action = clap::ArgAction::Version)]
     version: Option<bool>,
 
+    /// Ignore cached latest versions and query every source
+    #[arg(long, global = true)]
+    pub refresh: bool,
+
+    /// Use only cached latest versions, without network access
+    #[arg(long, global = true, conflicts_with = "refresh")]
+    pub offline: bool,
+
+    /// Print the full error chain for failed latest-version lookups
+    #[arg(long, global = true)]
+    pub verbose: bool,
+
+    /// Suppress the banner and progress spinners
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// When to use colored output [default: auto, or output.color from the config]
+    #[arg(long, global = true, value_enum)]
+    pub color: Option<ColorChoice>,
+
+    /// Disable colored output (same as --color=never)
+    #[arg(long, global = true, conflicts_with = "color")]
+    pub no_color: bool,
+
+    /// Include tools hidden with `ai-cli-apps hide`
+    #[arg(long, global = true)]
+    pub show_hidden: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Check latest versions available
-    Check,
-    /// Upgrade AI CLI tools (optionally specify tool name, e.g., 'amp')
+    /// Check latest versions available, exiting non-zero when tools need attention
+    Check {
+        /// Tools that must be installed; checks every installed tool when omitted
+        tools: Vec<String>,
+        /// Conditions that produce a non-zero exit code (default: all)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        fail_on: Vec<FailOn>,
+    },
+    /// Upgrade AI CLI tools (specify tool names, e.g., 'amp', or --all)
     Upgrade {
-        /// Optional tool name to upgrade directly (e.g., 'amp')
-        tool: Option<String>,
+        #[command(flatten)]
+        selection: ToolSelection,
+        /// Only upgrade tools whose new release is within this level
+        #[arg(long, value_enum)]
+        policy: Option<UpgradePolicy>,
+        /// Apply the configured policies to named tools too (used by scheduled runs)
+        #[arg(long, hide = true)]
+        respect_policy: bool,
     },
     /// Update AI CLI tools (alias for upgrade)
     Update {
-        /// Optional tool name to update directly (e.g., 'amp')
-        tool: Option<String>,
+        #[command(flatten)]
+        selection: ToolSelection,
+        /// Only update tools whose new release is within this level
+        #[arg(long, value_enum)]
+        policy: Option<UpgradePolicy>,
+        /// Apply the configured policies to named tools too (used by scheduled runs)
+        #[arg(long, hide = true)]
+        respect_policy: bool,
     },
-    /// Install AI CLI tools (optionally specify tool name, e.g., 'claude')
+    /// Install AI CLI tools (optionally specify tool names, e.g., 'claude codex')
     Install {
-        /// Optional tool name to install directly (e.g., 'claude')
-        tool: Option<String>,
+        #[command(flatten)]
+        selection: ToolSelection,
+        /// Install method to use when the tool supports several (e.g., 'npm')
+        #[arg(long, value_enum)]
+        via: Option<MethodKind>,
+        /// Don't ask for confirmation with --all or --all-except
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Install AI CLI tools (alias for install)
     Add {
-        /// Optional tool name to install directly (e.g., 'claude')
-        tool: Option<String>,
+        #[command(flatten)]
+        selection: ToolSelection,
+        /// Install method to use when the tool supports several (e.g., 'npm')
+        #[arg(long, value_enum)]
+        via: Option<MethodKind>,
+        /// Don't ask for confirmation with --all or --all-except
+        #[arg(short, long)]
+        yes: bool,
     },
-    /// Uninstall AI CLI tools (optionally specify tool name, e.g., 'claude')
+    /// Uninstall AI CLI tools (optionally specify tool names, e.g., 'claude codex')
     Uninstall {
-        /// Optional tool name to uninstall directly (e.g., 'claude')
-        tool: Option<String>,
+        #[command(flatten)]
+        selection: ToolSelection,
         /// Remove config directory (will ask for confirmation unless --force is used)
         #[arg(long)]
         remove_config: bool,
+        /// Don't ask for confirmation with --all or --all-except
+        #[arg(short, long)]
+        yes: bool,
         /// Skip all confirmation prompts
         #[arg(long)]
         force: bool,
     },
     /// Uninstall AI CLI tools (alias for uninstall)
     Remove {
-        /// Optional tool name to uninstall directly (e.g., 'claude')
-        tool: Option<String>,
+        #[command(flatten)]
+        selection: ToolSelection,
         /// Remove config directory (will ask for confirmation unless --force is used)
         #[arg(long)]
         remove_config: bool,
+        /// Don't ask for confirmation with --all or --all-except
+        #[arg(short, long)]
+        yes: bool,
         /// Skip all confirmation prompts
         #[arg(long)]
         force: bool,
     },
     /// List installed AI CLI tools (alias for default command)
     List,
+    /// Leave tools out of the list, the install menu and --all (e.g., 'kilo cline')
+    Hide {
+        #[arg(required = true)]
+        tools: Vec<String>,
+    },
+    /// Stop hiding tools hidden with `hide`
+    Show {
+        #[arg(required = true)]
+        tools: Vec<String>,
+    },
+    /// Show or change settings in the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Show past installs, uninstalls and upgrades
+    History {
+        /// Only show operations on this tool (e.g., 'claude')
+        tool: Option<String>,
+        /// Number of most recent operations to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Show everything known about a tool: what it is, where it's installed, its config and updates
+    Info {
+        /// Tool name (e.g., 'gemini')
+        tool: String,
+        /// Print the details as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show release notes between the installed and latest version of a tool
+    Changelog {
+        /// Tool name (e.g., 'codex')
+        tool: String,
+    },
+    /// Upgrade tools automatically with a systemd user timer, or cron where systemd isn't available
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+    /// Show a pending update notice and refresh it in the background when due (for shell hooks)
+    Notify {
+        /// Check for updates now instead of in the background
+        #[arg(long, hide = true)]
+        run_check: bool,
+    },
+    /// Print the man page, or a Markdown command reference with --markdown
+    Man {
+        /// Emit a Markdown reference instead of roff
+        #[arg(long)]
+        markdown: bool,
+        /// Write one man page per command (or ai-cli-apps.md) into this directory
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+    },
+    /// Print the shell completion script, or install it with --install
+    Completions {
+        shell: Shell,
+        /// Write the script to the shell's completion directory instead of printing it
+        #[arg(long)]
+        install: bool,
+    },
+}
+
+/// The full CLI definition, with the supported tools listed from the catalog in the long help.
+pub fn command() -> Command {
+    let tools: Vec<String> = tools::catalog()
+        .iter()
+        .map(|tool| {
+            let binary_name = tool.binary_name.as_deref().unwrap_or(&tool.name);
+            format!("  {} ({})", tool.name, binary_name)
+        })
+        .collect();
+    Cli::command().long_about(format!(
+        "Check and manage AI CLI tools versions\n\nSupported tools:\n{}",
+        tools.join("\n")
+    ))
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print a setting's effective value (e.g., 'cache.ttl_minutes')
+    Get { key: String },
+    /// Change a setting, e.g. 'tools.claude.channel stable' or 'install.preference brew,npm'
+    Set { key: String, value: String },
+    /// Remove a setting from the config file, restoring its default
+    Unset { key: String },
+    /// List every setting with its effective value
+    List,
+    /// Open the config file in $VISUAL or $EDITOR and validate it afterwards
+    Edit,
+}
+
+#[derive(Subcommand)]
+pub enum ScheduleAction {
+    /// Install the scheduled upgrade, replacing an existing one
+    Enable {
+        #[command(flatten)]
+        frequency: FrequencyArgs,
+        #[command(flatten)]
+        job: ScheduledJob,
+    },
+    /// Show the installed schedule, its next run and how the last run went
+    Status,
+    /// Remove the scheduled upgrade
+    Disable,
+    /// Run the scheduled upgrade now, appending its output to the log
+    #[command(hide = true)]
+    Run {
+        #[command(flatten)]
+        job: ScheduledJob,
+    },
+}
+
+/// How often the scheduled upgrade runs; daily unless one of these is given.
+#[derive(Args, Debug)]
+#[group(multiple = false)]
+pub struct FrequencyArgs {
+    /// Run every hour
+    #[arg(long)]
+    pub hourly: bool,
+    /// Run once a day (default)
+    #[arg(long)]
+    pub daily: bool,
+    /// Run once a week
+    #[arg(long)]
+    pub weekly: bool,
+}
+
+impl FrequencyArgs {
+    pub fn frequency(&self) -> Frequency {
+        if self.hourly {
+            Frequency::Hourly
+        } else if self.weekly {
+            Frequency::Weekly
+        } else {
+            Frequency::Daily
+        }
+    }
+}
+
+/// What the scheduled upgrade does.
+#[derive(Args, Debug)]
+pub struct ScheduledJob {
+    /// Only upgrade these tools; every installed tool when omitted
+    pub tools: Vec<String>,
+    /// Which new releases to install [default: upgrade.policy and tools.<name>.policy from the
+    /// config]
+    #[arg(long, value_enum)]
+    pub policy: Option<UpgradePolicy>,
+}
+
+/// Which tools a command acts on. With none of these set, the command asks interactively.
+#[derive(Args, Debug)]
+pub struct ToolSelection {
+    /// Tool names (e.g., 'claude codex')
+    pub tools: Vec<String>,
+    /// Act on every applicable tool
+    #[arg(long, conflicts_with = "tools")]
+    pub all: bool,
+    /// Act on every applicable tool except these (comma-separated)
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["tools", "all"])]
+    pub all_except: Vec<String>,
+}
+
+impl ToolSelection {
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty() && !self.all && self.all_except.is_empty()
+    }
 }
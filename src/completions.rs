@@ -0,0 +1,196 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use clap_complete::{
+    CompletionCandidate,
+    engine::ArgValueCandidates,
+    env::{self, CompleteEnv, EnvCompleter},
+};
+use colored::*;
+
+use crate::{
+    cli, output, paths,
+    tools::{self, Tool},
+};
+
+const BIN: &str = "ai-cli-apps";
+const VAR: &str = "COMPLETE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Elvish,
+    Powershell,
+}
+
+impl Shell {
+    fn completer(self) -> &'static dyn EnvCompleter {
+        match self {
+            Shell::Bash => &env::Bash,
+            Shell::Zsh => &env::Zsh,
+            Shell::Fish => &env::Fish,
+            Shell::Elvish => &env::Elvish,
+            Shell::Powershell => &env::Powershell,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        self.completer().name()
+    }
+}
+
+/// Answers a completion request from the shell script and exits, if this is one.
+/// Must run before anything is written to stdout.
+pub fn complete() {
+    CompleteEnv::with_factory(command).var(VAR).complete();
+}
+
+/// `Cli` with tool-name completions attached. Install offers every catalog tool; uninstall
+/// and upgrade only offer tools found on `PATH`.
+fn command() -> clap::Command {
+    let all = || ArgValueCandidates::new(|| tool_candidates(|_| true));
+    let installed = || ArgValueCandidates::new(|| tool_candidates(is_installed));
+
+    let mut command = cli::command();
+    for name in ["install", "add"] {
+        command = command.mut_subcommand(name, |sub| {
+            sub.mut_arg("tools", |arg| arg.add(all()))
+                .mut_arg("all_except", |arg| arg.add(all()))
+        });
+    }
+    for name in ["uninstall", "remove", "upgrade", "update"] {
+        command = command.mut_subcommand(name, |sub| {
+            sub.mut_arg("tools", |arg| arg.add(installed()))
+                .mut_arg("all_except", |arg| arg.add(installed()))
+        });
+    }
+    for name in ["hide", "show"] {
+        command = command.mut_subcommand(name, |sub| sub.mut_arg("tools", |arg| arg.add(all())));
+    }
+    for name in ["info", "changelog", "history"] {
+        command = command.mut_subcommand(name, |sub| sub.mut_arg("tool", |arg| arg.add(all())));
+    }
+    command.mut_subcommand("check", |sub| sub.mut_arg("tools", |arg| arg.add(all())))
+}
+
+fn is_installed(tool: &Tool) -> bool {
+    tool.binary_path().is_some()
+}
+
+/// Binary names, plus single-word display names that differ from them, for matching tools.
+fn tool_candidates(filter: impl Fn(&Tool) -> bool) -> Vec<CompletionCandidate> {
+    let mut candidates = Vec::new();
+    for tool in tools::catalog().iter().filter(|tool| filter(tool)) {
+        let help = Some(tool.name.clone().into());
+        if let Some(binary_name) = &tool.binary_name {
+            candidates.push(CompletionCandidate::new(binary_name).help(help.clone()));
+        }
+        let name = tool.name.to_lowercase();
+        if !name.contains(' ') && tool.binary_name.as_deref() != Some(name.as_str()) {
+            candidates.push(CompletionCandidate::new(name).help(help));
+        }
+    }
+    candidates
+}
+
+/// Writes the script that registers completions for `shell`.
+pub fn print(shell: Shell) -> Result<()> {
+    output::write_stdout(|stdout| {
+        shell
+            .completer()
+            .write_registration(VAR, BIN, BIN, BIN, stdout)
+    })
+    .context("Failed to write completion script")
+}
+
+/// Writes the completion script where `shell` loads it from.
+pub fn install(shell: Shell) -> Result<()> {
+    let path = match shell {
+        Shell::Bash => paths::data_home()?
+            .join("bash-completion/completions")
+            .join(BIN),
+        Shell::Zsh => paths::home_dir()?.join(".zfunc").join(format!("_{}", BIN)),
+        Shell::Fish => paths::config_home()?
+            .join("fish/completions")
+            .join(format!("{}.fish", BIN)),
+        Shell::Elvish | Shell::Powershell => anyhow::bail!(
+            "{} has no standard completion directory. Load the output of `{} completions {}` from your profile instead.",
+            shell.name(),
+            BIN,
+            shell.name()
+        ),
+    };
+
+    let mut script = Vec::new();
+    shell
+        .completer()
+        .write_registration(VAR, BIN, BIN, BIN, &mut script)
+        .context("Failed to generate completion script")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&path, script)
+        .with_context(|| format!("Failed to write completion script {}", path.display()))?;
+
+    println!(
+        "{} Installed {} completions to {}",
+        "✓".green(),
+        shell.name(),
+        path.display()
+    );
+    if shell == Shell::Zsh {
+        println!(
+            "{} Add `fpath=(~/.zfunc $fpath)` before `compinit` in ~/.zshrc if it isn't there yet",
+            "→".cyan()
+        );
+    }
+    println!("{} Restart your shell to pick them up", "→".cyan());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use clap_complete::engine;
+
+    use super::{command, is_installed};
+    use crate::tools;
+
+    fn complete(args: &[&str]) -> Vec<String> {
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        let index = args.len() - 1;
+        engine::complete(&mut command(), args, index, None)
+            .unwrap()
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .filter(|value| !value.starts_with('-'))
+            .collect()
+    }
+
+    #[test]
+    fn it_offers_every_tool_to_install_but_only_installed_ones_to_remove() {
+        let binaries = |installed_only: bool| -> Vec<String> {
+            tools::catalog()
+                .iter()
+                .filter(|tool| !installed_only || is_installed(tool))
+                .filter_map(|tool| tool.binary_name.clone())
+                .collect()
+        };
+
+        assert_eq!(complete(&["ai-cli-apps", "install", ""]), binaries(false));
+        assert_eq!(complete(&["ai-cli-apps", "uninstall", ""]), binaries(true));
+        assert_eq!(
+            complete(&["ai-cli-apps", "upgrade", "--all-except", ""]),
+            binaries(true)
+        );
+        assert_eq!(
+            complete(&["ai-cli-apps", "install", "co"]),
+            ["codex", "copilot"]
+        );
+    }
+}
@@ -0,0 +1,705 @@
+use std::{collections::HashMap, fs, io, path::PathBuf, process::Command, time::Duration};
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use toml_edit::DocumentMut;
+
+use crate::{
+    actions::require_tool,
+    cli::ConfigAction,
+    output::ColorChoice,
+    paths,
+    tools::{self, MethodKind, Tool},
+    versions::UpgradePolicy,
+};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub output: OutputConfig,
+    pub install: InstallConfig,
+    pub upgrade: UpgradeConfig,
+    pub cache: CacheConfig,
+    pub http: HttpConfig,
+    pub registries: RegistryConfig,
+    pub notify: NotifyConfig,
+    pub tools: HashMap<String, ToolConfig>,
+}
+
+/// Defaults for `--color` and `--quiet`; the command-line flags take precedence.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub color: ColorChoice,
+    pub quiet: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InstallConfig {
+    /// Install methods to try in order when a tool supports several.
+    pub preference: Vec<MethodKind>,
+}
+
+/// Which releases `upgrade --all` and scheduled upgrades install, unless a tool overrides it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpgradeConfig {
+    pub policy: UpgradePolicy,
+    /// Releases published more recently than this are held back.
+    pub min_age_days: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// How long looked-up latest versions are reused before querying upstream again.
+    pub ttl_minutes: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { ttl_minutes: 60 }
+    }
+}
+
+impl CacheConfig {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_minutes * 60)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    pub connect_timeout_secs: u64,
+    /// Maximum time to wait between reads of a response body.
+    pub read_timeout_secs: u64,
+    /// Extra attempts for connection failures, timeouts, 429s and 5xx responses.
+    pub retries: u32,
+    /// Delay before the first retry; doubled for each further attempt.
+    pub retry_backoff_ms: u64,
+    /// Proxy for all requests. Defaults to the `HTTP(S)_PROXY`/`NO_PROXY` environment.
+    pub proxy: Option<String>,
+    /// PEM files with extra root certificates to trust, e.g. a corporate CA.
+    pub ca_certificates: Vec<PathBuf>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: 10,
+            read_timeout_secs: 30,
+            retries: 2,
+            retry_backoff_ms: 500,
+            proxy: None,
+            ca_certificates: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotifyConfig {
+    /// Check for updates in the background and mention them on the next run.
+    pub enabled: bool,
+    /// Minimum time between background checks.
+    pub interval_hours: u64,
+    /// Also send a desktop notification through `notify-send` (Linux only).
+    pub desktop: bool,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            desktop: false,
+        }
+    }
+}
+
+impl NotifyConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_hours * 3600)
+    }
+}
+
+/// Mirror overrides for networks that can't reach the public endpoints.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// npm registry base URL. Defaults to `registry=` from `~/.npmrc`.
+    pub npm: Option<String>,
+    /// GitHub API base URL, e.g. `https://github.example.com/api/v3` for GitHub Enterprise.
+    pub github_api: Option<String>,
+    /// Homebrew JSON API mirror, passed to brew as `HOMEBREW_API_DOMAIN`.
+    pub homebrew_api: Option<String>,
+    /// unpkg-style mirror serving files from npm packages, used for changelogs.
+    pub npm_cdn: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolConfig {
+    pub method: Option<MethodKind>,
+    /// Release channel to track, e.g. `stable`, `next` or `beta`.
+    pub channel: Option<String>,
+    /// Replacement URL for the tool's bootstrap or installer script.
+    pub installer_url: Option<String>,
+    pub policy: Option<UpgradePolicy>,
+    pub min_age_days: Option<u64>,
+    /// Version to stay on: installed where the method allows it, and never upgraded.
+    pub pin: Option<String>,
+    /// Leave the tool out of `list`, the install menu and `--all`.
+    pub hidden: bool,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let path = config_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+
+    /// Deserializes `content`, also rejecting `[tools]` tables for tools that don't exist,
+    /// which the schema alone can't express.
+    fn parse(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        check_tool_keys(config.tools.keys().map(String::as_str)).map_err(anyhow::Error::msg)?;
+        Ok(config)
+    }
+
+    /// Settings for `tool`, keyed by its name or binary name (case-insensitive).
+    pub fn tool(&self, tool: &Tool) -> Option<&ToolConfig> {
+        self.tools
+            .iter()
+            .find(|(key, _)| tool.matches(key))
+            .map(|(_, config)| config)
+    }
+
+    /// Settings for the catalog tool with display name `name`.
+    pub fn tool_named(&self, name: &str) -> Option<&ToolConfig> {
+        tools::catalog()
+            .iter()
+            .find(|tool| tool.name == name)
+            .and_then(|tool| self.tool(tool))
+    }
+
+    pub fn installer_url(&self, tool: &Tool) -> Option<&str> {
+        self.tool(tool)
+            .and_then(|config| config.installer_url.as_deref())
+    }
+
+    pub fn channel(&self, tool: &Tool) -> Option<&str> {
+        self.tool(tool).and_then(|config| config.channel.as_deref())
+    }
+
+    /// The version `tool` is pinned to, if any.
+    pub fn pin(&self, tool: &Tool) -> Option<&str> {
+        self.tool(tool).and_then(|config| config.pin.as_deref())
+    }
+
+    /// Upgrade policy for the catalog tool with display name `name`.
+    pub fn upgrade_policy(&self, name: &str) -> UpgradePolicy {
+        self.tool_named(name)
+            .and_then(|config| config.policy)
+            .unwrap_or(self.upgrade.policy)
+    }
+
+    /// Minimum release age before the catalog tool with display name `name` is upgraded.
+    pub fn min_release_age(&self, name: &str) -> Duration {
+        let days = self
+            .tool_named(name)
+            .and_then(|config| config.min_age_days)
+            .unwrap_or(self.upgrade.min_age_days);
+        Duration::from_secs(days * 24 * 3600)
+    }
+
+    /// Whether the catalog tool with display name `name` was hidden with `ai-cli-apps hide`.
+    pub fn is_hidden(&self, name: &str) -> bool {
+        self.tool_named(name).is_some_and(|config| config.hidden)
+    }
+
+    /// Treats every tool as visible for the rest of the run, for `--show-hidden`.
+    pub fn show_hidden(&mut self) {
+        for config in self.tools.values_mut() {
+            config.hidden = false;
+        }
+    }
+
+    pub fn preferred_install_method(&self, tool: &Tool) -> MethodKind {
+        self.tool(tool)
+            .and_then(|config| config.method)
+            .filter(|kind| tool.install_method(*kind).is_some())
+            .unwrap_or_else(|| {
+                tool.preferred_install_method(&self.install.preference)
+                    .kind()
+            })
+    }
+}
+
+pub fn config_path() -> Result<PathBuf> {
+    Ok(paths::config_dir()?.join("config.toml"))
+}
+
+pub fn handle_config_command(action: &ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Get { key } => get(key),
+        ConfigAction::Set { key, value } => set(key, value),
+        ConfigAction::Unset { key } => unset(key),
+        ConfigAction::List => list(),
+        ConfigAction::Edit => edit(),
+    }
+}
+
+/// Hides tools from `list`, the install menu and `--all`, or shows them again.
+pub fn handle_hide_command(names: &[String], hidden: bool) -> Result<()> {
+    let catalog = tools::catalog();
+    let targets = names
+        .iter()
+        .map(|name| require_tool(&catalog, name))
+        .collect::<Result<Vec<_>>>()?;
+    let (path, mut document) = read_document()?;
+    for tool in &targets {
+        set_hidden(&mut document, tool, hidden)?;
+    }
+    validate(&document).map_err(|e| anyhow::anyhow!("{} is invalid: {}", path.display(), e))?;
+    write_document(&path, &document)?;
+
+    let names: Vec<&str> = targets.iter().map(|tool| tool.name.as_str()).collect();
+    if hidden {
+        println!(
+            "{} Hidden {}. Use --show-hidden to include them, or `ai-cli-apps show` to undo.",
+            "✓".green(),
+            names.join(", ")
+        );
+    } else {
+        println!("{} Showing {} again", "✓".green(), names.join(", "));
+    }
+    Ok(())
+}
+
+/// Sets or removes `hidden` in the tool's table, under whichever key the file already uses
+/// for it. A table left empty is removed too.
+fn set_hidden(document: &mut DocumentMut, tool: &Tool, hidden: bool) -> Result<()> {
+    let existing = document
+        .get("tools")
+        .and_then(|item| item.as_table_like())
+        .and_then(|tools| tools.iter().find(|(key, _)| tool.matches(key)))
+        .map(|(key, _)| key.to_string());
+    let key = existing.unwrap_or_else(|| {
+        tool.binary_name
+            .clone()
+            .unwrap_or_else(|| tool.name.to_lowercase())
+    });
+
+    if hidden {
+        return insert(
+            document,
+            &["tools".to_string(), key, "hidden".to_string()],
+            true.into(),
+        );
+    }
+    if let Some(tools) = document
+        .get_mut("tools")
+        .and_then(|item| item.as_table_like_mut())
+        && let Some(table) = tools
+            .get_mut(&key)
+            .and_then(|item| item.as_table_like_mut())
+    {
+        table.remove("hidden");
+        if table.is_empty() {
+            tools.remove(&key);
+        }
+    }
+    Ok(())
+}
+
+/// Prints the effective value of `key`, which may be a default not written in the file.
+fn get(key: &str) -> Result<()> {
+    let effective = effective_settings()?;
+    match lookup(&effective, &split_key(key)?) {
+        Some(toml::Value::String(value)) => println!("{}", value),
+        Some(toml::Value::Table(table)) => {
+            for (name, value) in flatten(table, key) {
+                println!("{} = {}", name, value);
+            }
+        }
+        Some(value) => println!("{}", value),
+        // Options without a default, like `http.proxy`, are simply unset.
+        None => check_known_key(key)?,
+    }
+    Ok(())
+}
+
+fn set(key: &str, value: &str) -> Result<()> {
+    let (path, document) = read_document()?;
+    let (document, written) = set_value(&document, key, value)?;
+    write_document(&path, &document)?;
+    println!("{} {} = {}", "✓".green(), key, written);
+    Ok(())
+}
+
+/// `document` with `key` set to `value`, typed as the schema expects, and the value as
+/// written. Fails with the schema's explanation for unknown keys and invalid values.
+fn set_value(document: &DocumentMut, key: &str, value: &str) -> Result<(DocumentMut, String)> {
+    let segments = split_key(key)?;
+    let mut first_error = None;
+    for candidate in candidate_values(value) {
+        let mut attempt = document.clone();
+        insert(&mut attempt, &segments, candidate.clone())?;
+        match validate(&attempt) {
+            Ok(()) => return Ok((attempt, candidate.to_string().trim().to_string())),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    anyhow::bail!(
+        "Invalid setting {} = {}: {}",
+        key,
+        value,
+        first_error.unwrap_or_default()
+    )
+}
+
+fn unset(key: &str) -> Result<()> {
+    let segments = split_key(key)?;
+    let (path, mut document) = read_document()?;
+    let (last, parents) = segments.split_last().expect("split_key returns segments");
+
+    let mut table = document.as_table_mut();
+    for segment in parents {
+        match table.get_mut(segment).and_then(|item| item.as_table_mut()) {
+            Some(child) => table = child,
+            None => return not_set(key),
+        }
+    }
+    if table.remove(last).is_none() {
+        return not_set(key);
+    }
+    write_document(&path, &document)?;
+    println!("{} Unset {}", "✓".green(), key);
+    Ok(())
+}
+
+fn not_set(key: &str) -> Result<()> {
+    check_known_key(key)?;
+    println!("{} {} is not set in the config file", "!".yellow(), key);
+    Ok(())
+}
+
+/// Prints every setting with its effective value.
+fn list() -> Result<()> {
+    println!(
+        "{}",
+        format!("# {}", config_path()?.display()).bright_black()
+    );
+    for (key, value) in flatten(&effective_settings()?, "") {
+        println!("{} = {}", key, value);
+    }
+    Ok(())
+}
+
+/// Opens the config file in `$VISUAL` or `$EDITOR` and validates it afterwards.
+fn edit() -> Result<()> {
+    let path = config_path()?;
+    if !path.exists() {
+        write_document(
+            &path,
+            &"# ai-cli-apps settings. `ai-cli-apps config list` shows every key.\n"
+                .parse()
+                .expect("template is valid TOML"),
+        )?;
+    }
+
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .find_map(|var| {
+            std::env::var(var)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        })
+        .unwrap_or_else(|| "vi".to_string());
+    // Editors are often configured with arguments, e.g. `code --wait`.
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to run editor `{}`", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor `{}` exited with {}", editor, status);
+    }
+
+    let problem = match read_document() {
+        Err(e) => Some(format!("{:#}", e)),
+        Ok((_, document)) => validate(&document)
+            .err()
+            .map(|e| format!("{} is invalid: {}", path.display(), e)),
+    };
+    if let Some(problem) = problem {
+        anyhow::bail!(
+            "{}\nRun `ai-cli-apps config edit` again to fix it.",
+            problem
+        );
+    }
+    println!("{} Saved {}", "✓".green(), path.display());
+    Ok(())
+}
+
+fn split_key(key: &str) -> Result<Vec<String>> {
+    let segments: Vec<String> = key.split('.').map(str::to_string).collect();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        anyhow::bail!(
+            "Invalid config key '{}'; use dotted names like cache.ttl_minutes",
+            key
+        );
+    }
+    Ok(segments)
+}
+
+/// Values to try for `config set`, in order: the text as a TOML value (number, bool,
+/// array...), as a plain string, and as a comma-separated list of strings.
+fn candidate_values(value: &str) -> Vec<toml_edit::Value> {
+    let mut candidates = Vec::new();
+    if let Ok(mut parsed) = value.parse::<toml_edit::Value>() {
+        parsed.decor_mut().clear();
+        candidates.push(parsed);
+    }
+    candidates.push(value.into());
+    candidates.push(
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect::<toml_edit::Array>()
+            .into(),
+    );
+    candidates
+}
+
+fn insert(document: &mut DocumentMut, segments: &[String], value: toml_edit::Value) -> Result<()> {
+    let (last, parents) = segments.split_last().expect("split_key returns segments");
+    let mut table = document.as_table_mut();
+    for segment in parents {
+        let item = table.entry(segment).or_insert_with(|| {
+            let mut child = toml_edit::Table::new();
+            child.set_implicit(true);
+            toml_edit::Item::Table(child)
+        });
+        table = item
+            .as_table_mut()
+            .with_context(|| format!("'{}' in the config file is not a table", segment))?;
+    }
+    table.insert(last, toml_edit::value(value));
+    Ok(())
+}
+
+/// Checks `document` against the config schema, returning the first problem.
+fn validate(document: &DocumentMut) -> Result<(), String> {
+    if let Some(tools) = document.get("tools").and_then(|item| item.as_table_like()) {
+        check_tool_keys(tools.iter().map(|(key, _)| key))?;
+    }
+    toml::from_str::<Config>(&document.to_string())
+        .map(|_| ())
+        .map_err(|e| e.message().to_string())
+}
+
+/// Fails on a `[tools]` key that doesn't name a catalog tool, or on two keys for the same
+/// tool (e.g. `claude` and `"claude code"`), where it would be unclear which table applies.
+fn check_tool_keys<'a>(keys: impl Iterator<Item = &'a str>) -> Result<(), String> {
+    let catalog = tools::catalog();
+    // Sorted so the same file always reports the same key.
+    let mut keys: Vec<&str> = keys.collect();
+    keys.sort_unstable();
+    let mut seen: Vec<(&str, &Tool)> = Vec::new();
+    for key in keys {
+        let Some(tool) = catalog.iter().find(|tool| tool.matches(key)) else {
+            return Err(format!("unknown tool `{}` in [tools]", key));
+        };
+        if let Some((other, _)) = seen.iter().find(|(_, seen)| seen.name == tool.name) {
+            return Err(format!(
+                "`{}` and `{}` in [tools] both configure {}; keep one of them",
+                other, key, tool.name
+            ));
+        }
+        seen.push((key, tool));
+    }
+    Ok(())
+}
+
+/// Fails with the schema's explanation if `key` isn't a setting at all.
+fn check_known_key(key: &str) -> Result<()> {
+    let mut probe = DocumentMut::new();
+    insert(&mut probe, &split_key(key)?, "".into())?;
+    match validate(&probe) {
+        Err(e) if e.starts_with("unknown") => {
+            anyhow::bail!("Unknown config key '{}': {}", key, e)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn read_document() -> Result<(PathBuf, DocumentMut)> {
+    let path = config_path()?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to read config file {}", path.display()));
+        }
+    };
+    let document = content
+        .parse()
+        .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+    Ok((path, document))
+}
+
+fn write_document(path: &PathBuf, document: &DocumentMut) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, document.to_string())
+        .with_context(|| format!("Failed to write config file {}", path.display()))
+}
+
+/// The loaded config, defaults included, as a TOML table.
+fn effective_settings() -> Result<toml::Table> {
+    match toml::Value::try_from(Config::load()?)? {
+        toml::Value::Table(table) => Ok(table),
+        _ => unreachable!("Config serializes to a table"),
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, segments: &[String]) -> Option<&'a toml::Value> {
+    let (first, rest) = segments.split_first()?;
+    let mut value = table.get(first)?;
+    for (index, segment) in rest.iter().enumerate() {
+        let table = value.as_table()?;
+        value = if index == 0 && first == "tools" {
+            // `[tools]` is keyed by whichever name the user wrote, matched like the loader does.
+            table
+                .iter()
+                .find(|(name, _)| same_tool(name, segment))
+                .map(|(_, config)| config)?
+        } else {
+            table.get(segment)?
+        };
+    }
+    Some(value)
+}
+
+fn same_tool(a: &str, b: &str) -> bool {
+    tools::catalog()
+        .iter()
+        .find(|tool| tool.matches(a))
+        .is_some_and(|tool| tool.matches(b))
+        || a.eq_ignore_ascii_case(b)
+}
+
+/// `key = value` lines for every leaf under `table`, with dotted keys.
+fn flatten(table: &toml::Table, prefix: &str) -> Vec<(String, toml::Value)> {
+    let mut lines = Vec::new();
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match value {
+            toml::Value::Table(child) => lines.extend(flatten(child, &key)),
+            _ => lines.push((key, value.clone())),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, set_hidden, set_value};
+    use crate::tools;
+    use toml_edit::DocumentMut;
+
+    fn set(document: &str, key: &str, value: &str) -> Result<String, String> {
+        let document: DocumentMut = document.parse().unwrap();
+        set_value(&document, key, value)
+            .map(|(document, _)| document.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn it_sets_values_with_the_type_the_schema_expects() {
+        let document = "# keep me\n[cache]\nttl_minutes = 60\n";
+        assert_eq!(
+            set(document, "cache.ttl_minutes", "30").unwrap(),
+            "# keep me\n[cache]\nttl_minutes = 30\n"
+        );
+        assert!(
+            set("", "install.preference", "brew,npm")
+                .unwrap()
+                .contains(r#"preference = ["brew", "npm"]"#)
+        );
+        assert!(
+            set("", "tools.claude.pin", "1.0")
+                .unwrap()
+                .contains(r#"pin = "1.0""#)
+        );
+
+        let unknown = set("", "cache.ttl", "30").unwrap_err();
+        assert!(unknown.contains("unknown field `ttl`"), "{}", unknown);
+        let invalid = set("", "cache.ttl_minutes", "soon").unwrap_err();
+        assert!(invalid.contains("expected u64"), "{}", invalid);
+        let tool = set("", "tools.nope.pin", "1").unwrap_err();
+        assert!(tool.contains("unknown tool `nope`"), "{}", tool);
+    }
+
+    #[test]
+    fn it_hides_tools_under_the_key_the_file_already_uses() {
+        let catalog = tools::catalog();
+        let kilo = catalog.iter().find(|tool| tool.matches("kilo")).unwrap();
+        let cline = catalog.iter().find(|tool| tool.matches("cline")).unwrap();
+        let mut document: DocumentMut = "[tools.Kilo]\nchannel = \"next\"\n".parse().unwrap();
+
+        set_hidden(&mut document, kilo, true).unwrap();
+        set_hidden(&mut document, cline, true).unwrap();
+        assert_eq!(
+            document.to_string(),
+            "[tools.Kilo]\nchannel = \"next\"\nhidden = true\n\n[tools.cline]\nhidden = true\n"
+        );
+
+        set_hidden(&mut document, kilo, false).unwrap();
+        set_hidden(&mut document, cline, false).unwrap();
+        assert_eq!(document.to_string(), "[tools.Kilo]\nchannel = \"next\"\n");
+    }
+
+    #[test]
+    fn it_rejects_unknown_tools_when_loading() {
+        let config = Config::parse("[tools.claude]\nchannel = \"stable\"\n").unwrap();
+        assert!(config.tool_named("Claude Code").is_some());
+
+        let error = Config::parse("[tools.cluade]\nchannel = \"stable\"\n").unwrap_err();
+        assert_eq!(error.to_string(), "unknown tool `cluade` in [tools]");
+    }
+
+    #[test]
+    fn it_rejects_two_tables_for_the_same_tool() {
+        let content = "[tools.\"claude code\"]\nchannel = \"latest\"\n\n[tools.claude]\nchannel = \"stable\"\n";
+        let error = Config::parse(content).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`claude` and `claude code` in [tools] both configure Claude Code; keep one of them"
+        );
+    }
+}
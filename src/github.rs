@@ -0,0 +1,180 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex, OnceLock},
+};
+
+use chrono::{Local, TimeZone};
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use serde::Deserialize;
+
+use crate::tools::command_output;
+
+pub const API_BASE: &str = "https://api.github.com";
+
+static TOKEN: OnceLock<Option<String>> = OnceLock::new();
+static ENTERPRISE_TOKENS: LazyLock<Mutex<HashMap<String, Option<String>>>> =
+    LazyLock::new(Default::default);
+
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+}
+
+/// A token from `GITHUB_TOKEN`, `GH_TOKEN` or `gh auth token`, looked up once per run.
+/// Authenticated requests get 5000 calls an hour instead of 60 per IP.
+pub fn token() -> Option<&'static str> {
+    TOKEN
+        .get_or_init(|| {
+            ["GITHUB_TOKEN", "GH_TOKEN"]
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|token| !token.is_empty()))
+                .or_else(|| command_output("gh", &["auth", "token"]).filter(|t| !t.is_empty()))
+        })
+        .as_deref()
+}
+
+/// `gh auth token --hostname <host>`, looked up once per host and run.
+fn enterprise_token(host: &str) -> Option<String> {
+    let mut tokens = ENTERPRISE_TOKENS.lock().unwrap_or_else(|e| e.into_inner());
+    tokens
+        .entry(host.to_string())
+        .or_insert_with_key(|host| {
+            command_output("gh", &["auth", "token", "--hostname", host]).filter(|t| !t.is_empty())
+        })
+        .clone()
+}
+
+/// The token for requests to `api_base`. The github.com token from `public` only ever goes
+/// to [`API_BASE`]; any other base gets `enterprise(host)`, which is empty unless `gh` is
+/// logged in to that host, so mirrors and proxies get no credentials.
+fn token_for(
+    api_base: &str,
+    public: impl FnOnce() -> Option<String>,
+    enterprise: impl FnOnce(&str) -> Option<String>,
+) -> Option<String> {
+    if api_base == API_BASE {
+        return public();
+    }
+    let url = reqwest::Url::parse(api_base).ok()?;
+    enterprise(url.host_str()?)
+}
+
+/// Headers for requests to the GitHub API at `api_base`.
+pub fn api_headers(api_base: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::ACCEPT,
+        HeaderValue::from_static("application/vnd.github+json"),
+    );
+    let token = token_for(api_base, || token().map(str::to_string), enterprise_token);
+    if let Some(value) = token.and_then(|t| format!("Bearer {}", t).parse().ok()) {
+        headers.insert(header::AUTHORIZATION, value);
+    }
+    headers
+}
+
+/// The `rel="next"` URL from a paginated response's `Link` header.
+pub fn next_page(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(header::LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#)
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// The `x-ratelimit-*` headers of an API response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub remaining: Option<u64>,
+    /// Seconds since the Unix epoch when the quota refills.
+    pub resets_at: Option<u64>,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let number = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+        };
+        Self {
+            remaining: number("x-ratelimit-remaining"),
+            resets_at: number("x-ratelimit-reset"),
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    pub fn describe(&self) -> String {
+        let reset = self
+            .resets_at
+            .and_then(|secs| Local.timestamp_opt(secs as i64, 0).single())
+            .map(|time| format!(", resets at {}", time.format("%H:%M")))
+            .unwrap_or_default();
+        let hint = if token().is_none() {
+            " (set GITHUB_TOKEN or run `gh auth login`)"
+        } else {
+            ""
+        };
+        format!("rate limited{}{}", reset, hint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{API_BASE, next_page, token_for};
+    use reqwest::header::{HeaderMap, LINK};
+
+    #[test]
+    fn it_only_sends_the_github_token_to_the_public_api() {
+        let token = |api_base: &str| {
+            token_for(
+                api_base,
+                || Some("public".to_string()),
+                |host| (host == "github.corp.example").then(|| "enterprise".to_string()),
+            )
+        };
+        assert_eq!(token(API_BASE).as_deref(), Some("public"));
+        assert_eq!(
+            token("https://github.corp.example/api/v3").as_deref(),
+            Some("enterprise")
+        );
+        assert_eq!(token("https://github-mirror.example/api"), None);
+        assert_eq!(token("not a url"), None);
+    }
+
+    #[test]
+    fn it_finds_the_next_page_in_the_link_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(next_page(&headers), None);
+        headers.insert(
+            LINK,
+            r#"<https://api.github.com/repositories/1/releases?page=2>; rel="next", <https://api.github.com/repositories/1/releases?page=9>; rel="last""#
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            next_page(&headers).as_deref(),
+            Some("https://api.github.com/repositories/1/releases?page=2")
+        );
+    }
+}
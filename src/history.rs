@@ -0,0 +1,342 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::require_tool,
+    paths,
+    tools::{self, Detection, MethodKind, Tool},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Install,
+    Uninstall,
+    Upgrade,
+}
+
+impl Action {
+    fn as_str(self) -> &'static str {
+        match self {
+            Action::Install => "install",
+            Action::Uninstall => "uninstall",
+            Action::Upgrade => "upgrade",
+        }
+    }
+}
+
+/// A command run as part of an operation.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommandRecord {
+    pub command: String,
+    /// `None` when the command couldn't be started or was killed by a signal.
+    pub exit_code: Option<i32>,
+}
+
+/// One line of `~/.local/state/ai-cli-apps/history.jsonl`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Entry {
+    /// RFC 3339 local time when the operation started.
+    pub timestamp: String,
+    pub action: Action,
+    pub tool: String,
+    pub method: MethodKind,
+    pub version_before: Option<String>,
+    pub version_after: Option<String>,
+    #[serde(default)]
+    pub commands: Vec<CommandRecord>,
+    #[serde(default)]
+    pub removed: Vec<PathBuf>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// An install, uninstall or upgrade in progress, collecting what it does for the history log.
+pub struct Operation {
+    entry: Entry,
+}
+
+impl Operation {
+    pub fn start(action: Action, tool: &Tool, method: MethodKind, detection: &Detection) -> Self {
+        Self {
+            entry: Entry {
+                timestamp: chrono::Local::now().to_rfc3339(),
+                action,
+                tool: tool.name.clone(),
+                method,
+                version_before: detection
+                    .version(tool)
+                    .and_then(|version| version.installed.clone()),
+                version_after: None,
+                commands: Vec::new(),
+                removed: Vec::new(),
+                success: false,
+                error: None,
+            },
+        }
+    }
+
+    /// Runs `command` with inherited stdio and records it with its exit code.
+    pub fn run(&mut self, command: &mut Command) -> io::Result<ExitStatus> {
+        let description = describe(command);
+        self.run_as(command, description)
+    }
+
+    /// Like [`Operation::run`], recording `description` instead of the literal command line,
+    /// e.g. to name the URL a temporary script came from.
+    pub fn run_as(&mut self, command: &mut Command, description: String) -> io::Result<ExitStatus> {
+        let status = command.status();
+        self.entry.commands.push(CommandRecord {
+            command: description,
+            exit_code: status.as_ref().ok().and_then(|status| status.code()),
+        });
+        status
+    }
+
+    pub fn removed(&mut self, path: &Path) {
+        self.entry.removed.push(path.to_path_buf());
+    }
+
+    /// Appends the operation to the history log, with the version installed afterwards.
+    /// A log that can't be written is reported but doesn't fail the operation.
+    pub async fn finish(mut self, tool: &Tool, result: &Result<()>) {
+        self.entry.version_after = tools::detect_version(tool).await;
+        self.entry.success = result.is_ok();
+        self.entry.error = result.as_ref().err().map(|e| format!("{:#}", e));
+        if let Err(e) = append(&self.entry) {
+            eprintln!("{} Failed to record history: {:#}", "!".yellow(), e);
+        }
+    }
+}
+
+fn describe(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            if arg.contains(char::is_whitespace) {
+                format!("'{}'", arg)
+            } else {
+                arg.into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn history_path() -> Result<PathBuf> {
+    Ok(paths::state_dir()?.join("history.jsonl"))
+}
+
+fn append(entry: &Entry) -> Result<()> {
+    append_to(&history_path()?, entry)
+}
+
+fn append_to(path: &Path, entry: &Entry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Every recorded operation, oldest first. Lines that don't parse are skipped.
+pub fn load() -> Result<Vec<Entry>> {
+    load_from(&history_path()?)
+}
+
+fn load_from(path: &Path) -> Result<Vec<Entry>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Prints the last `limit` operations, optionally only those on one tool.
+pub fn handle_history_command(tool_name: Option<&str>, limit: usize) -> Result<()> {
+    let catalog = tools::catalog();
+    let tool = tool_name
+        .map(|name| require_tool(&catalog, name))
+        .transpose()?;
+    let (entries, total) = select(load()?, tool.map(|tool| tool.name.as_str()), limit);
+
+    if entries.is_empty() {
+        println!("{}", "No operations recorded yet.".yellow());
+        return Ok(());
+    }
+
+    let width = entries.iter().map(|e| e.tool.len()).max().unwrap_or(0);
+    for entry in &entries {
+        print_entry(entry, width);
+    }
+    if total > entries.len() {
+        println!(
+            "\n{}",
+            format!(
+                "Showing the last {} of {} operations (use --limit to see more)",
+                limit, total
+            )
+            .bright_black()
+        );
+    }
+    Ok(())
+}
+
+/// The last `limit` entries, on the tool with display name `tool` if given, and how many
+/// matched before the limit.
+fn select(entries: Vec<Entry>, tool: Option<&str>, limit: usize) -> (Vec<Entry>, usize) {
+    let mut entries: Vec<Entry> = entries
+        .into_iter()
+        .filter(|entry| tool.is_none_or(|tool| entry.tool == tool))
+        .collect();
+    let total = entries.len();
+    entries.drain(..total.saturating_sub(limit));
+    (entries, total)
+}
+
+fn print_entry(entry: &Entry, width: usize) {
+    let mark = if entry.success {
+        "✓".green()
+    } else {
+        "✗".red()
+    };
+    let when = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| entry.timestamp.clone());
+    let versions = match (&entry.version_before, &entry.version_after) {
+        (Some(before), Some(after)) if before == after => before.clone(),
+        (before, after) => format!(
+            "{} → {}",
+            before.as_deref().unwrap_or("none"),
+            after.as_deref().unwrap_or("none")
+        ),
+    };
+
+    println!(
+        "{} {}  {:9} {:width$}  {}{}",
+        mark,
+        when.bright_black(),
+        entry.action.as_str(),
+        entry.tool.bold(),
+        versions,
+        format!(" via {}", entry.method).bright_black(),
+        width = width
+    );
+    for command in &entry.commands {
+        let exit = match command.exit_code {
+            Some(code) => format!("exit {}", code),
+            None => "no exit code".to_string(),
+        };
+        println!(
+            "    {} {}",
+            format!("$ {}", command.command).bright_black(),
+            format!("({})", exit).bright_black()
+        );
+    }
+    for path in &entry.removed {
+        println!("    {} {}", "removed".bright_black(), path.display());
+    }
+    if let Some(error) = &entry.error {
+        println!("    {}", error.red());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+
+    use super::{Action, CommandRecord, Entry, append_to, load_from, select};
+    use crate::tools::MethodKind;
+
+    fn entry(action: Action, tool: &str, version_after: &str) -> Entry {
+        Entry {
+            timestamp: "2026-10-01T12:00:00+00:00".to_string(),
+            action,
+            tool: tool.to_string(),
+            method: MethodKind::Npm,
+            version_before: None,
+            version_after: Some(version_after.to_string()),
+            commands: vec![CommandRecord {
+                command: "npm install -g cline".to_string(),
+                exit_code: Some(0),
+            }],
+            removed: Vec::new(),
+            success: true,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn it_appends_entries_and_skips_lines_that_dont_parse() {
+        let path = std::env::temp_dir()
+            .join(format!("ai-cli-apps-history-{}", std::process::id()))
+            .join("history.jsonl");
+        assert!(load_from(&path).unwrap().is_empty());
+
+        append_to(&path, &entry(Action::Install, "Cline CLI", "1.0.0")).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{{\"timestamp\": truncated").unwrap();
+        append_to(&path, &entry(Action::Upgrade, "Cline CLI", "1.1.0")).unwrap();
+
+        let entries = load_from(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, Action::Install);
+        assert_eq!(entries[1].version_after.as_deref(), Some("1.1.0"));
+        assert_eq!(entries[1].commands[0].exit_code, Some(0));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn it_keeps_the_last_entries_for_the_chosen_tool() {
+        let entries = || {
+            vec![
+                entry(Action::Install, "Amp", "1.0.0"),
+                entry(Action::Install, "Cline CLI", "1.0.0"),
+                entry(Action::Upgrade, "Amp", "1.1.0"),
+                entry(Action::Upgrade, "Amp", "1.2.0"),
+            ]
+        };
+        let versions = |entries: &[Entry]| -> Vec<String> {
+            entries
+                .iter()
+                .map(|e| format!("{} {}", e.tool, e.version_after.as_deref().unwrap()))
+                .collect()
+        };
+
+        let (shown, total) = select(entries(), Some("Amp"), 2);
+        assert_eq!(versions(&shown), ["Amp 1.1.0", "Amp 1.2.0"]);
+        assert_eq!(total, 3);
+
+        let (shown, total) = select(entries(), None, 10);
+        assert_eq!(shown.len(), 4);
+        assert_eq!(total, 4);
+
+        let (shown, total) = select(entries(), Some("Codex CLI"), 10);
+        assert!(shown.is_empty());
+        assert_eq!(total, 0);
+    }
+}
@@ -0,0 +1,143 @@
+use std::{fs, sync::OnceLock, time::Duration};
+
+use anyhow::{Context, Result};
+use reqwest::{Certificate, Client, NoProxy, Proxy, Response, StatusCode, header::HeaderMap};
+
+use crate::config::HttpConfig;
+
+static CLIENT: OnceLock<HttpClient> = OnceLock::new();
+
+/// The shared HTTP client used for every version lookup and script download.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl HttpClient {
+    pub fn from_config(config: &HttpConfig) -> Result<Self> {
+        let mut builder = Client::builder()
+            .user_agent(concat!("ai-cli-apps/", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+            .read_timeout(Duration::from_secs(config.read_timeout_secs));
+
+        // Without an explicit proxy reqwest already honours HTTP(S)_PROXY and NO_PROXY.
+        if let Some(proxy) = &config.proxy {
+            let proxy = Proxy::all(proxy)
+                .with_context(|| format!("Invalid proxy URL '{}'", proxy))?
+                .no_proxy(NoProxy::from_env());
+            builder = builder.proxy(proxy);
+        }
+
+        for path in &config.ca_certificates {
+            let pem = fs::read(path)
+                .with_context(|| format!("Failed to read CA certificate {}", path.display()))?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("Invalid PEM in CA certificate {}", path.display()))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        Ok(Self {
+            client: builder.build().context("Failed to build HTTP client")?,
+            retries: config.retries,
+            backoff: Duration::from_millis(config.retry_backoff_ms),
+        })
+    }
+
+    /// GETs `url`, retrying connection failures, timeouts, 429s and 5xx responses with
+    /// exponential backoff. Other responses, including 4xx, are returned as-is.
+    pub async fn get(&self, url: &str, headers: HeaderMap) -> reqwest::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let result = self.client.get(url).headers(headers.clone()).send().await;
+            let retryable = match &result {
+                Ok(response) => is_retryable_status(response.status()),
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            if !retryable || attempt >= self.retries {
+                return result;
+            }
+
+            tokio::time::sleep(self.backoff * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Builds the shared client from config. Must be called before any request is made.
+pub fn init(config: &HttpConfig) -> Result<()> {
+    let client = HttpClient::from_config(config)?;
+    let _ = CLIENT.set(client);
+    Ok(())
+}
+
+/// The shared client, falling back to default settings if `init` was never called.
+pub fn client() -> &'static HttpClient {
+    CLIENT.get_or_init(|| {
+        HttpClient::from_config(&HttpConfig::default()).expect("default HTTP client must build")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpClient;
+    use crate::config::HttpConfig;
+    use httpmock::prelude::*;
+    use reqwest::header::HeaderMap;
+
+    #[tokio::test]
+    async fn it_retries_server_errors_up_to_the_limit() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/flaky");
+                then.status(503);
+            })
+            .await;
+
+        let client = HttpClient::from_config(&HttpConfig {
+            retries: 2,
+            retry_backoff_ms: 1,
+            ..HttpConfig::default()
+        })
+        .unwrap();
+        let response = client
+            .get(&server.url("/flaky"), HeaderMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 503);
+        mock.assert_calls_async(3).await;
+    }
+
+    #[tokio::test]
+    async fn it_does_not_retry_client_errors() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/missing");
+                then.status(404);
+            })
+            .await;
+
+        let client = HttpClient::from_config(&HttpConfig {
+            retry_backoff_ms: 1,
+            ..HttpConfig::default()
+        })
+        .unwrap();
+        let response = client
+            .get(&server.url("/missing"), HeaderMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 404);
+        mock.assert_calls_async(1).await;
+    }
+}
@@ -0,0 +1,500 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+
+use crate::{
+    actions::require_tool,
+    cache::{self, format_age},
+    config::Config,
+    history::{self, Action, Entry},
+    output,
+    registry::Registries,
+    tools::{self, DEFAULT_CHANNEL, InstallMethod, MethodKind, Tool, ToolVersion},
+    versions::{CacheMode, check_latest_versions, is_outdated, npm_dist_tags},
+};
+
+/// Everything known about one tool, as printed by `info` and emitted by `info --json`.
+#[derive(Debug, Serialize)]
+struct Report {
+    name: String,
+    command: String,
+    description: Option<String>,
+    vendor: Option<String>,
+    homepage: Option<String>,
+    docs_url: Option<String>,
+    license: Option<String>,
+    runtime: Option<String>,
+    install_methods: Vec<Method>,
+    preferred_method: MethodKind,
+    /// `None` when no binary was found and the version command didn't answer.
+    installed: Option<Installed>,
+    /// Config directories that exist, with their size on disk.
+    config_dirs: Vec<DirSize>,
+    latest: Latest,
+    last_upgrade: Option<Entry>,
+    auth: Vec<Credential>,
+}
+
+#[derive(Debug, Serialize)]
+struct Method {
+    kind: MethodKind,
+    /// e.g. `npm: @google/gemini-cli`.
+    description: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Installed {
+    version: Option<String>,
+    /// Raw output of the version command.
+    version_output: Option<String>,
+    /// Every copy found; the first is the one that runs.
+    binaries: Vec<Binary>,
+    method: Option<MethodKind>,
+    /// The npm package or Homebrew formula the install method manages.
+    package: Option<String>,
+    /// Versions the bootstrap installer keeps next to the active one.
+    retained_versions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Binary {
+    path: PathBuf,
+    /// Where `path` resolves to, when it's a symlink.
+    target: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct DirSize {
+    path: PathBuf,
+    bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Latest {
+    channel: String,
+    version: Option<String>,
+    /// RFC 3339 publish time, if the source reports it.
+    published_at: Option<String>,
+    error: Option<String>,
+    /// Every npm dist-tag, for tools published to npm.
+    channels: BTreeMap<String, String>,
+    pin: Option<String>,
+    update_available: bool,
+    held_back: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Credential {
+    /// `env` or `file`.
+    kind: &'static str,
+    name: String,
+    present: bool,
+}
+
+/// Prints what a tool is, where and how it's installed, what it keeps on disk, whether an
+/// update is available and whether it has credentials.
+pub async fn handle_info_command(
+    name: &str,
+    json: bool,
+    config: &Config,
+    cache_mode: CacheMode,
+) -> Result<()> {
+    let catalog = tools::catalog();
+    let tool = require_tool(&catalog, name)?;
+    let report = build_report(tool, config, cache_mode).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+async fn build_report(tool: &Tool, config: &Config, cache_mode: CacheMode) -> Result<Report> {
+    let installed = installed(tool).await?;
+
+    let mut version = ToolVersion::new(&tool.name)
+        .with_installed(installed.as_ref().and_then(|i| i.version.clone()))
+        .with_method(installed.as_ref().and_then(|i| i.method))
+        .with_channel(config.channel(tool));
+    check_latest_versions(std::slice::from_mut(&mut version), config, cache_mode).await;
+    let channels = match (cache_mode, npm_package(tool)) {
+        (CacheMode::Offline, _) | (_, None) => BTreeMap::new(),
+        (_, Some(package)) => {
+            let url = Registries::resolve(&config.registries).npm_package_url(package);
+            npm_dist_tags(&url).await.unwrap_or_default()
+        }
+    };
+
+    // The last upgrade is one optional field, so an unreadable history doesn't fail `info`.
+    let last_upgrade = match history::load() {
+        Ok(entries) => entries
+            .into_iter()
+            .rev()
+            .find(|entry| entry.tool == tool.name && entry.action == Action::Upgrade),
+        Err(error) => {
+            if output::verbose() {
+                eprintln!("{} {:#}", "Could not read the history:".yellow(), error);
+            }
+            None
+        }
+    };
+
+    let mut auth: Vec<Credential> = tool
+        .auth_env
+        .iter()
+        .map(|var| Credential {
+            kind: "env",
+            name: var.clone(),
+            present: std::env::var_os(var).is_some_and(|value| !value.is_empty()),
+        })
+        .collect();
+    auth.extend(tool.auth_paths()?.into_iter().map(|path| Credential {
+        kind: "file",
+        name: path.display().to_string(),
+        present: path.is_file(),
+    }));
+
+    Ok(Report {
+        name: tool.name.clone(),
+        command: tool
+            .binary_name
+            .clone()
+            .unwrap_or_else(|| tool.name.clone()),
+        description: tool.description.clone(),
+        vendor: tool.vendor.clone(),
+        homepage: tool.homepage.clone(),
+        docs_url: tool.docs_url.clone(),
+        license: tool.license.clone(),
+        runtime: tool.runtime.clone(),
+        install_methods: tool
+            .install_methods
+            .iter()
+            .map(|method| Method {
+                kind: method.kind(),
+                description: method.describe(),
+            })
+            .collect(),
+        preferred_method: config.preferred_install_method(tool),
+        installed,
+        config_dirs: tool
+            .config_paths()?
+            .into_iter()
+            .filter(|path| path.exists())
+            .map(|path| DirSize {
+                bytes: disk_usage(&path),
+                path,
+            })
+            .collect(),
+        latest: Latest {
+            channel: version
+                .channel
+                .clone()
+                .unwrap_or_else(|| DEFAULT_CHANNEL.to_string()),
+            published_at: version
+                .latest_published
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0))
+                .map(|time| time.to_rfc3339()),
+            error: version.latest_error.as_ref().map(|e| e.reason.clone()),
+            channels,
+            pin: config.pin(tool).map(str::to_string),
+            update_available: is_outdated(&version),
+            held_back: version.held_back.as_ref().map(ToString::to_string),
+            version: version.latest,
+        },
+        last_upgrade,
+        auth,
+    })
+}
+
+/// Where and how `tool` is installed, or `None` if it isn't.
+async fn installed(tool: &Tool) -> Result<Option<Installed>> {
+    let (version_output, version) = match tools::version_output(tool).await {
+        Some((output, version)) => (Some(output), version),
+        None => (None, None),
+    };
+    let binaries: Vec<Binary> = tool
+        .binary_paths()
+        .into_iter()
+        .map(|path| Binary {
+            target: path.canonicalize().ok().filter(|target| *target != path),
+            path,
+        })
+        .collect();
+    if binaries.is_empty() && version.is_none() {
+        return Ok(None);
+    }
+
+    let method = tool.detect_install_method();
+    let versions_dir = tool.versions_dir()?;
+    let mut retained_versions: Vec<String> = fs::read_dir(&versions_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    retained_versions.sort();
+
+    Ok(Some(Installed {
+        version,
+        version_output,
+        binaries,
+        method: method.map(InstallMethod::kind),
+        package: method.and_then(|method| match method {
+            InstallMethod::Npm(package) | InstallMethod::Brew(package) => Some(package.clone()),
+            InstallMethod::Bootstrap(_) | InstallMethod::Amp(_) => None,
+        }),
+        retained_versions,
+    }))
+}
+
+fn npm_package(tool: &Tool) -> Option<&str> {
+    match tool.install_method(MethodKind::Npm)? {
+        InstallMethod::Npm(package) => Some(package),
+        _ => None,
+    }
+}
+
+/// Total size of the files under `path`, without following symlinks. Unreadable entries
+/// count as empty.
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| disk_usage(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    format!("{:.1} {}", size, unit)
+}
+
+fn print_report(report: &Report) {
+    println!("{} ({})", report.name.bold(), report.command);
+    if let Some(description) = &report.description {
+        println!("  {}", description);
+    }
+    println!();
+    for (label, value) in [
+        ("Vendor", &report.vendor),
+        ("Homepage", &report.homepage),
+        ("Docs", &report.docs_url),
+        ("License", &report.license),
+    ] {
+        if let Some(value) = value {
+            field(label, value);
+        }
+    }
+    field(
+        "Requires",
+        report.runtime.as_deref().unwrap_or("nothing else"),
+    );
+    let methods: Vec<String> = report
+        .install_methods
+        .iter()
+        .map(|method| {
+            if method.kind == report.preferred_method && report.install_methods.len() > 1 {
+                format!("{} (preferred)", method.description)
+            } else {
+                method.description.clone()
+            }
+        })
+        .collect();
+    field("Install", &methods.join(", "));
+
+    section("Installed");
+    match &report.installed {
+        None => println!("  {}", "not installed".bright_black()),
+        Some(installed) => print_installed(installed),
+    }
+    match &report.last_upgrade {
+        Some(entry) => field("Upgraded", &describe_upgrade(entry)),
+        None if report.installed.is_some() => field("Upgraded", "not recorded in the history"),
+        None => {}
+    }
+
+    section("Config");
+    if report.config_dirs.is_empty() {
+        println!("  {}", "none found".bright_black());
+    }
+    let width = report
+        .config_dirs
+        .iter()
+        .map(|dir| dir.path.display().to_string().len())
+        .max()
+        .unwrap_or(0);
+    for dir in &report.config_dirs {
+        println!(
+            "  {:width$}  {}",
+            dir.path.display(),
+            format_size(dir.bytes).bright_black(),
+            width = width
+        );
+    }
+
+    section("Latest");
+    print_latest(&report.latest);
+
+    section("Auth");
+    if report.auth.is_empty() {
+        println!("  {}", "no known credentials".bright_black());
+    }
+    let width = report
+        .auth
+        .iter()
+        .map(|credential| credential.name.len())
+        .max()
+        .unwrap_or(0);
+    for credential in &report.auth {
+        let status = match (credential.kind, credential.present) {
+            ("env", true) => "set".green(),
+            ("env", false) => "not set".bright_black(),
+            (_, true) => "found".green(),
+            (_, false) => "missing".bright_black(),
+        };
+        println!("  {:width$}  {}", credential.name, status, width = width);
+    }
+}
+
+fn print_installed(installed: &Installed) {
+    let output = installed
+        .version_output
+        .as_deref()
+        .or(installed.version.as_deref())
+        .unwrap_or("unknown (the version command failed)");
+    let mut lines = output.lines();
+    field("Version", lines.next().unwrap_or_default());
+    for line in lines {
+        println!("  {:9} {}", "", line);
+    }
+    for (index, binary) in installed.binaries.iter().enumerate() {
+        let label = if index == 0 { "Binary" } else { "Shadowed" };
+        match &binary.target {
+            Some(target) => field(
+                label,
+                &format!("{} → {}", binary.path.display(), target.display()),
+            ),
+            None => field(label, &binary.path.display().to_string()),
+        }
+    }
+    if let Some(method) = installed.method {
+        match &installed.package {
+            Some(package) => field("Method", &format!("{} ({})", method, package)),
+            None => field("Method", method.as_str()),
+        }
+    }
+    if !installed.retained_versions.is_empty() {
+        field("Retained", &installed.retained_versions.join(", "));
+    }
+}
+
+fn print_latest(latest: &Latest) {
+    field("Channel", &latest.channel);
+    match &latest.version {
+        Some(version) => {
+            let released = latest
+                .published_at
+                .as_deref()
+                .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| {
+                    let age = cache::now().saturating_sub(time.timestamp().max(0) as u64);
+                    format!(", released {} ago", format_age(Duration::from_secs(age)))
+                })
+                .unwrap_or_default();
+            field("Version", &format!("{}{}", version, released));
+        }
+        None => field(
+            "Version",
+            &format!(
+                "unknown ({})",
+                latest.error.as_deref().unwrap_or("no cached version")
+            ),
+        ),
+    }
+    if !latest.channels.is_empty() {
+        let channels: Vec<String> = latest
+            .channels
+            .iter()
+            .map(|(channel, version)| format!("{} {}", channel, version))
+            .collect();
+        field("Channels", &channels.join(", "));
+    }
+    field("Pinned", latest.pin.as_deref().unwrap_or("no"));
+    let upgrade = match (&latest.held_back, latest.update_available) {
+        (Some(reason), _) => format!("held back: {}", reason),
+        (None, true) => "available".to_string(),
+        (None, false) if latest.version.is_some() => "up to date".to_string(),
+        (None, false) => "unknown".to_string(),
+    };
+    field("Upgrade", &upgrade);
+}
+
+fn describe_upgrade(entry: &Entry) -> String {
+    let when = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| entry.timestamp.clone());
+    format!(
+        "{}, {} → {}{}",
+        when,
+        entry.version_before.as_deref().unwrap_or("none"),
+        entry.version_after.as_deref().unwrap_or("none"),
+        if entry.success { "" } else { " (failed)" }
+    )
+}
+
+fn section(title: &str) {
+    println!("\n{}", format!("{}:", title).bold());
+}
+
+fn field(label: &str, value: &str) {
+    println!("  {:9} {}", format!("{}:", label).bright_black(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_size;
+
+    #[test]
+    fn it_formats_sizes_in_binary_units() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
@@ -1,38 +1,138 @@
 mod actions;
+mod cache;
+mod changelog;
 mod cli;
+mod completions;
+mod config;
+mod github;
+mod history;
+mod http;
+mod info;
+mod manual;
+mod notifier;
+mod output;
+mod paths;
+mod registry;
+mod schedule;
 mod tools;
 mod versions;
 
-use actions::{handle_install_command, handle_uninstall_command, handle_upgrade_command};
+use actions::{
+    handle_install_command, handle_uninstall_command, handle_upgrade_command, require_tool,
+};
 use anyhow::Result;
-use clap::Parser;
-use cli::{Cli, Commands};
+use clap::FromArgMatches;
+use cli::{Cli, Commands, ScheduleAction};
 use colored::*;
-use indicatif::{ProgressBar, ProgressStyle};
-use tools::installed_versions;
-use versions::{check_latest_versions, print_version};
+use config::Config;
+use output::{ColorChoice, Progress};
+use tools::Detection;
+use versions::{
+    CacheMode, check_exit_code, check_latest_versions, print_cache_notice, print_lookup_errors,
+    print_version,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
-    println!("\n{}", "🤖 AI Tools Manager".bright_cyan().bold());
-    println!("{}\n", "=".repeat(19).bright_cyan());
+    completions::complete();
+    let cli = Cli::from_arg_matches(&cli::command().get_matches()).unwrap_or_else(|e| e.exit());
+    // Loaded before anything is printed for the output defaults, but only required once the
+    // commands that can run without a valid config are out of the way.
+    let config = Config::load();
+    let defaults = config.as_ref().ok().map(|config| &config.output);
+    // JSON goes to stdout on its own, without the banner.
+    let json = matches!(cli.command, Some(Commands::Info { json: true, .. }));
+    let quiet = cli.quiet || json || defaults.is_some_and(|output| output.quiet);
+    let color = if cli.no_color {
+        ColorChoice::Never
+    } else {
+        cli.color
+            .or(defaults.map(|output| output.color))
+            .unwrap_or_default()
+    };
+    output::init(quiet, cli.verbose, color);
+    // Generated scripts and docs go to stdout, so they must not be preceded by the banner.
+    match &cli.command {
+        Some(Commands::Completions { shell, install }) => {
+            return if *install {
+                completions::install(*shell)
+            } else {
+                completions::print(*shell)
+            };
+        }
+        Some(Commands::Config { action }) => return config::handle_config_command(action),
+        Some(Commands::Hide { tools }) => return config::handle_hide_command(tools, true),
+        Some(Commands::Show { tools }) => return config::handle_hide_command(tools, false),
+        Some(Commands::Man { markdown, out_dir }) => {
+            match (markdown, out_dir) {
+                (false, None) => manual::print_man()?,
+                (true, None) => manual::print_markdown()?,
+                (false, Some(dir)) => {
+                    manual::write_man_pages(dir)?;
+                    println!("{} Wrote man pages to {}", "✓".green(), dir.display());
+                }
+                (true, Some(dir)) => {
+                    let path = manual::write_markdown(dir)?;
+                    println!("{} Wrote {}", "✓".green(), path.display());
+                }
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+    let mut config = config?;
+    if cli.show_hidden {
+        config.show_hidden();
+    }
+    http::init(&config.http)?;
+    let cache_mode = if cli.offline {
+        CacheMode::Offline
+    } else if cli.refresh {
+        CacheMode::Refresh
+    } else {
+        CacheMode::Default
+    };
+    // Run from shell hooks, timers and cron, so these print nothing but their own output.
+    match &cli.command {
+        Some(Commands::Notify { run_check }) => {
+            if *run_check {
+                notifier::run_check(&config).await?;
+            } else {
+                notifier::notify(&config);
+            }
+            return Ok(());
+        }
+        Some(Commands::Schedule {
+            action: ScheduleAction::Run { job },
+        }) => return schedule::run(job),
+        _ => {}
+    }
+    if !quiet {
+        println!("\n{}", "🤖 AI Tools Manager".bright_cyan().bold());
+        println!("{}\n", "=".repeat(19).bright_cyan());
+    }
 
+    let mut exit_code = 0;
+    let mut shown_versions = false;
     match cli.command {
+        Some(Commands::Completions { .. })
+        | Some(Commands::Config { .. })
+        | Some(Commands::Hide { .. })
+        | Some(Commands::Show { .. })
+        | Some(Commands::Man { .. })
+        | Some(Commands::Notify { .. }) => {
+            unreachable!("handled before the banner")
+        }
         None | Some(Commands::List) => {
-            let spinner = ProgressBar::new_spinner();
-            spinner.set_style(
-                ProgressStyle::default_spinner()
-                    .template("{spinner:.cyan} {msg}")
-                    .unwrap(),
-            );
-            spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-            spinner.set_message("Checking installed tools...");
-
-            let mut tools = installed_versions();
-            spinner.finish_and_clear();
+            let mut tools = detect_installed(&config).await.into_versions();
+            let hidden: Vec<String> = tools
+                .iter()
+                .filter(|tool| config.is_hidden(&tool.name))
+                .map(|tool| tool.name.clone())
+                .collect();
+            tools.retain(|tool| !hidden.contains(&tool.name));
 
-            check_latest_versions(&mut tools).await;
+            check_latest_versions(&mut tools, &config, cache_mode).await;
 
             let label_width = tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
             let id_width = tools
@@ -70,21 +170,41 @@ async fn main() -> Result<()> {
                     print_version(tool, true, label_width, id_width);
                 }
             }
+            if !hidden.is_empty() {
+                println!(
+                    "\n{}",
+                    format!(
+                        "Hidden: {} (use --show-hidden to include them)",
+                        hidden.join(", ")
+                    )
+                    .bright_black()
+                );
+            }
+            print_cache_notice(&tools, cache_mode);
+            if cli.verbose {
+                print_lookup_errors(&tools);
+            }
+            if config.notify.enabled {
+                let _ = notifier::record(&tools);
+            }
+            shown_versions = true;
         }
-        Some(Commands::Check) => {
-            let spinner = ProgressBar::new_spinner();
-            spinner.set_style(
-                ProgressStyle::default_spinner()
-                    .template("{spinner:.cyan} {msg}")
-                    .unwrap(),
-            );
-            spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-            spinner.set_message("Checking installed tools...");
+        Some(Commands::Check {
+            tools: names,
+            fail_on,
+        }) => {
+            let catalog = tools::catalog();
+            let required = names
+                .iter()
+                .map(|name| require_tool(&catalog, name).map(|tool| tool.name.clone()))
+                .collect::<Result<Vec<_>>>()?;
 
-            let mut tools = installed_versions();
-            spinner.finish_and_clear();
+            let mut tools = detect_installed(&config).await.into_versions();
+            if !required.is_empty() {
+                tools.retain(|tool| required.contains(&tool.name));
+            }
 
-            check_latest_versions(&mut tools).await;
+            check_latest_versions(&mut tools, &config, cache_mode).await;
             let label_width = tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
             let id_width = tools
                 .iter()
@@ -95,27 +215,99 @@ async fn main() -> Result<()> {
             for tool in &tools {
                 print_version(tool, true, label_width, id_width);
             }
+            print_cache_notice(&tools, cache_mode);
+            if cli.verbose {
+                print_lookup_errors(&tools);
+            }
+
+            shown_versions = true;
+            exit_code = check_exit_code(&tools, !required.is_empty(), &fail_on);
         }
-        Some(Commands::Upgrade { tool }) | Some(Commands::Update { tool }) => {
-            handle_upgrade_command(tool.as_deref()).await?;
+        Some(Commands::Schedule { action }) => match action {
+            ScheduleAction::Enable { frequency, job } => {
+                schedule::enable(frequency.frequency(), &job)?
+            }
+            ScheduleAction::Status => schedule::status()?,
+            ScheduleAction::Disable => schedule::disable()?,
+            ScheduleAction::Run { .. } => unreachable!("handled before the banner"),
+        },
+        Some(Commands::History { tool, limit }) => {
+            history::handle_history_command(tool.as_deref(), limit)?;
         }
-        Some(Commands::Install { tool }) | Some(Commands::Add { tool }) => {
-            handle_install_command(tool.as_deref()).await?;
+        Some(Commands::Info { tool, json }) => {
+            info::handle_info_command(&tool, json, &config, cache_mode).await?
+        }
+        Some(Commands::Changelog { tool }) => {
+            let detection = detect_installed(&config).await;
+            changelog::handle_changelog_command(&tool, &config, &detection, cache_mode).await?;
+        }
+        Some(Commands::Upgrade {
+            selection,
+            policy,
+            respect_policy,
+        })
+        | Some(Commands::Update {
+            selection,
+            policy,
+            respect_policy,
+        }) => {
+            let detection = detect_installed(&config).await;
+            handle_upgrade_command(
+                &selection,
+                policy,
+                respect_policy,
+                &config,
+                &detection,
+                cache_mode,
+            )
+            .await?;
+        }
+        Some(Commands::Install {
+            selection,
+            via,
+            yes,
+        })
+        | Some(Commands::Add {
+            selection,
+            via,
+            yes,
+        }) => {
+            let detection = detect_installed(&config).await;
+            handle_install_command(&selection, via, yes, &config, &detection).await?;
         }
         Some(Commands::Uninstall {
-            tool,
+            selection,
             remove_config,
+            yes,
             force,
         })
         | Some(Commands::Remove {
-            tool,
+            selection,
             remove_config,
+            yes,
             force,
         }) => {
-            handle_uninstall_command(tool.as_deref(), remove_config, force).await?;
+            let detection = detect_installed(&config).await;
+            handle_uninstall_command(&selection, remove_config, yes, force, &config, &detection)
+                .await?;
         }
     }
 
-    println!();
+    if !quiet {
+        println!();
+        if config.notify.enabled && !shown_versions {
+            notifier::notify(&config);
+        }
+    }
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
     Ok(())
 }
+
+async fn detect_installed(config: &Config) -> Detection {
+    let progress = Progress::start("Checking installed tools...");
+    let detection = Detection::run(config).await;
+    progress.finish();
+    detection
+}
@@ -0,0 +1,241 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::{Arg, Command};
+use clap_mangen::Man;
+
+use crate::{cli, output, tools};
+
+/// Prints the top-level man page as roff.
+pub fn print_man() -> Result<()> {
+    output::write_stdout(render_root_page).context("Failed to write man page")
+}
+
+/// Writes a man page for the top-level command and each subcommand into `dir`.
+pub fn write_man_pages(dir: &Path) -> Result<()> {
+    create_dir(dir)?;
+    clap_mangen::generate_to(built_command(), dir)
+        .with_context(|| format!("Failed to write man pages to {}", dir.display()))?;
+
+    let man = Man::new(built_command());
+    let path = dir.join(man.get_filename());
+    let mut file =
+        fs::File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    render_root_page(&mut file).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The top-level page, with the supported tools in their own section instead of the
+/// plain-text list from `--help`, which roff would run together into one paragraph.
+fn render_root_page(w: &mut dyn io::Write) -> io::Result<()> {
+    let man = Man::new(built_command().long_about(None));
+    man.render_title(w)?;
+    man.render_name_section(w)?;
+    man.render_synopsis_section(w)?;
+    man.render_description_section(w)?;
+    man.render_options_section(w)?;
+    man.render_subcommands_section(w)?;
+
+    writeln!(w, ".SH \"SUPPORTED TOOLS\"")?;
+    for tool in tools::catalog() {
+        let binary_name = tool.binary_name.as_deref().unwrap_or(&tool.name);
+        writeln!(w, ".TP")?;
+        writeln!(w, "\\fB{}\\fR", roff_escape(binary_name))?;
+        writeln!(
+            w,
+            "{} (install methods: {})",
+            roff_escape(&tool.name),
+            tool.available_methods()
+        )?;
+        if let Some(description) = &tool.description {
+            writeln!(w, ".br")?;
+            writeln!(w, "{}", roff_escape(description))?;
+        }
+    }
+
+    man.render_version_section(w)
+}
+
+fn roff_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('-', "\\-")
+}
+
+pub fn print_markdown() -> Result<()> {
+    output::write_stdout(|stdout| stdout.write_all(markdown().as_bytes()))
+        .context("Failed to write Markdown reference")
+}
+
+/// Writes the Markdown reference to `dir/ai-cli-apps.md` and returns its path.
+pub fn write_markdown(dir: &Path) -> Result<PathBuf> {
+    create_dir(dir)?;
+    let path = dir.join("ai-cli-apps.md");
+    fs::write(&path, markdown()).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn create_dir(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create directory {}", dir.display()))
+}
+
+fn built_command() -> Command {
+    let mut command = cli::command().disable_help_subcommand(true);
+    command.build();
+    command
+}
+
+/// Command reference with the supported-tools table from the catalog.
+fn markdown() -> String {
+    let command = built_command();
+    let name = command.get_name().to_string();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# {}\n", name);
+    if let Some(about) = command.get_about() {
+        let _ = writeln!(out, "{}\n", about);
+    }
+
+    out.push_str("## Supported tools\n\n");
+    out.push_str("| Tool | Command | Description | Install methods |\n");
+    out.push_str("|------|---------|-------------|-----------------|\n");
+    for tool in tools::catalog() {
+        let binary_name = tool.binary_name.as_deref().unwrap_or(&tool.name);
+        let _ = writeln!(
+            out,
+            "| {} | `{}` | {} | {} |",
+            match &tool.docs_url {
+                Some(url) => format!("[{}]({})", tool.name, url),
+                None => tool.name.clone(),
+            },
+            binary_name,
+            tool.description.as_deref().unwrap_or(""),
+            tool.available_methods()
+        );
+    }
+
+    out.push_str("\n## Usage\n\n");
+    write_usage(&mut out, &command);
+    write_arguments(&mut out, &command, "Global options", true);
+
+    out.push_str("## Commands\n\n");
+    for subcommand in command.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        let _ = writeln!(out, "### `{} {}`\n", name, subcommand.get_name());
+        if let Some(about) = subcommand.get_about() {
+            let _ = writeln!(out, "{}\n", about);
+        }
+        let aliases: Vec<_> = subcommand.get_visible_aliases().collect();
+        if !aliases.is_empty() {
+            let _ = writeln!(out, "Aliases: `{}`\n", aliases.join("`, `"));
+        }
+        write_usage(&mut out, subcommand);
+        write_arguments(&mut out, subcommand, "Options", false);
+    }
+    out
+}
+
+fn write_usage(out: &mut String, command: &Command) {
+    let usage = command.clone().render_usage().to_string();
+    let usage = usage.trim_start_matches("Usage: ");
+    let _ = writeln!(out, "```\n{}\n```\n", usage);
+}
+
+/// Lists `command`'s arguments, either only the global ones or everything else.
+fn write_arguments(out: &mut String, command: &Command, heading: &str, global: bool) {
+    let arguments: Vec<&Arg> = command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set() && arg.is_global_set() == global)
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+        .collect();
+    if arguments.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "#### {}\n", heading);
+    for arg in arguments {
+        let _ = write!(out, "- `{}`", arg_label(arg));
+        if let Some(help) = arg.get_help() {
+            let _ = write!(out, ": {}", help);
+        }
+        let values: Vec<_> = arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| format!("`{}`", value.get_name()))
+            .collect();
+        if !values.is_empty() {
+            let _ = write!(out, ". Possible values: {}", values.join(", "));
+        }
+        let defaults: Vec<_> = arg
+            .get_default_values()
+            .iter()
+            .map(|value| value.to_string_lossy())
+            .collect();
+        if !defaults.is_empty() && arg.get_action().takes_values() {
+            let _ = write!(out, ". Default: `{}`", defaults.join(","));
+        }
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
+fn arg_label(arg: &Arg) -> String {
+    let value_name = || {
+        arg.get_value_names()
+            .and_then(|names| names.first())
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| arg.get_id().as_str().to_uppercase())
+    };
+    if arg.is_positional() {
+        let multiple = arg
+            .get_num_args()
+            .is_some_and(|range| range.max_values() > 1);
+        return format!("<{}>{}", value_name(), if multiple { "..." } else { "" });
+    }
+
+    let mut label = String::new();
+    if let Some(short) = arg.get_short() {
+        let _ = write!(label, "-{}, ", short);
+    }
+    if let Some(long) = arg.get_long() {
+        let _ = write!(label, "--{}", long);
+    }
+    if arg.get_action().takes_values() {
+        let _ = write!(label, " <{}>", value_name());
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markdown;
+    use crate::tools;
+
+    #[test]
+    fn it_lists_every_catalog_tool_in_the_markdown_table() {
+        let markdown = markdown();
+        let rows: Vec<&str> = markdown
+            .lines()
+            .skip_while(|line| !line.starts_with("| Tool |"))
+            .skip(2)
+            .take_while(|line| line.starts_with('|'))
+            .collect();
+
+        let catalog = tools::catalog();
+        assert_eq!(rows.len(), catalog.len());
+        for (row, tool) in rows.iter().zip(&catalog) {
+            let name = match &tool.docs_url {
+                Some(url) => format!("[{}]({})", tool.name, url),
+                None => tool.name.clone(),
+            };
+            assert!(row.starts_with(&format!("| {} |", name)), "{}", row);
+            assert!(
+                row.ends_with(&format!("| {} |", tool.available_methods())),
+                "{}",
+                row
+            );
+        }
+        assert!(rows[0].contains("| `claude` |"));
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache,
+    config::Config,
+    paths,
+    tools::{Detection, ToolVersion},
+    versions::{CacheMode, check_latest_versions, is_outdated},
+};
+
+/// Result of the last update check, persisted to `~/.cache/ai-cli-apps/notifier.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NotifierState {
+    /// Seconds since the Unix epoch when the last check started.
+    checked_at: u64,
+    /// Tools that had a newer version available.
+    updates: Vec<String>,
+    /// Whether the notice for `updates` has been printed yet.
+    shown: bool,
+}
+
+impl NotifierState {
+    fn load() -> Self {
+        state_path()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default()
+    }
+
+    /// A missing or unreadable file is a state that has never checked.
+    fn load_from(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        self.save_to(&state_path()?)
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create cache directory {}", parent.display())
+            })?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn from_check(tools: &[ToolVersion], shown: bool) -> Self {
+        Self {
+            checked_at: cache::now(),
+            updates: tools
+                .iter()
+                .filter(|tool| is_outdated(tool))
+                .map(|tool| tool.name.clone())
+                .collect(),
+            shown,
+        }
+    }
+
+    fn is_due(&self, interval: Duration) -> bool {
+        cache::now().saturating_sub(self.checked_at) >= interval.as_secs()
+    }
+}
+
+fn state_path() -> Result<PathBuf> {
+    Ok(paths::cache_dir()?.join("notifier.json"))
+}
+
+/// Prints a pending update notice, then starts a background check if the last one is older
+/// than the configured interval. Never fails the command it runs after.
+pub fn notify(config: &Config) {
+    let mut state = NotifierState::load();
+    if !state.shown && !state.updates.is_empty() {
+        eprintln!("{}", notice(&state.updates).yellow());
+        state.shown = true;
+    }
+
+    if state.is_due(config.notify.interval()) && spawn_background_check() {
+        // Claim the slot now so concurrent invocations don't start checks of their own.
+        state.checked_at = cache::now();
+    }
+    let _ = state.save();
+}
+
+fn spawn_background_check() -> bool {
+    std::env::current_exe()
+        .and_then(|exe| {
+            Command::new(exe)
+                .args(["notify", "--run-check", "--quiet"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+        })
+        .is_ok()
+}
+
+/// Runs the update check in the foreground; this is what the background process does.
+pub async fn run_check(config: &Config) -> Result<()> {
+    let mut tools = Detection::run(config).await.into_versions();
+    tools.retain(|tool| !config.is_hidden(&tool.name));
+    check_latest_versions(&mut tools, config, CacheMode::Default).await;
+    let previous = NotifierState::load();
+    let state = NotifierState::from_check(&tools, false);
+    state.save()?;
+
+    if config.notify.desktop && !state.updates.is_empty() && state.updates != previous.updates {
+        send_desktop_notification(&state.updates);
+    }
+    Ok(())
+}
+
+/// Stores the outcome of a check the user has already seen, as with `list`, so the next run
+/// neither repeats the notice nor starts another check before the interval is up.
+pub fn record(tools: &[ToolVersion]) -> Result<()> {
+    NotifierState::from_check(tools, true).save()
+}
+
+fn notice(updates: &[String]) -> String {
+    let count = if updates.len() == 1 {
+        "1 AI tool has an update".to_string()
+    } else {
+        format!("{} AI tools have updates", updates.len())
+    };
+    format!(
+        "{} ({}). Run `ai-cli-apps` for details.",
+        count,
+        updates.join(", ")
+    )
+}
+
+fn send_desktop_notification(updates: &[String]) {
+    if cfg!(target_os = "linux") {
+        let _ = Command::new("notify-send")
+            .args(["--app-name=ai-cli-apps", "AI CLI tools", &notice(updates)])
+            .status();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use super::{NotifierState, notice};
+    use crate::{cache, tools::ToolVersion};
+
+    fn tool(name: &str, installed: &str, latest: &str) -> ToolVersion {
+        let mut tool = ToolVersion::new(name).with_installed(Some(installed.to_string()));
+        tool.latest = Some(latest.to_string());
+        tool
+    }
+
+    #[test]
+    fn it_is_due_once_the_interval_has_passed_since_the_last_check() {
+        let hour = Duration::from_secs(3600);
+        assert!(NotifierState::default().is_due(hour));
+
+        let mut state = NotifierState {
+            checked_at: cache::now(),
+            ..NotifierState::default()
+        };
+        assert!(!state.is_due(hour));
+        state.checked_at -= 3600;
+        assert!(state.is_due(hour));
+        // A check stamped in the future, e.g. after clock skew, isn't due yet.
+        state.checked_at = cache::now() + 60;
+        assert!(!state.is_due(hour));
+    }
+
+    #[test]
+    fn it_round_trips_the_state_and_treats_a_corrupt_file_as_never_checked() {
+        let path = std::env::temp_dir()
+            .join(format!("ai-cli-apps-notifier-{}", std::process::id()))
+            .join("notifier.json");
+        let tools = [
+            tool("Amp", "1.0.0", "1.1.0"),
+            tool("Codex CLI", "0.46.0", "0.46.0"),
+        ];
+
+        let state = NotifierState::from_check(&tools, false);
+        assert_eq!(state.updates, ["Amp"]);
+        state.save_to(&path).unwrap();
+        let loaded = NotifierState::load_from(&path);
+        assert_eq!(loaded.checked_at, state.checked_at);
+        assert_eq!(loaded.updates, ["Amp"]);
+        assert!(!loaded.shown);
+
+        fs::write(&path, "{not json").unwrap();
+        let corrupt = NotifierState::load_from(&path);
+        assert_eq!(corrupt.checked_at, 0);
+        assert!(corrupt.updates.is_empty());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn it_counts_updates_in_the_notice() {
+        assert_eq!(
+            notice(&["Amp".to_string()]),
+            "1 AI tool has an update (Amp). Run `ai-cli-apps` for details."
+        );
+        assert_eq!(
+            notice(&["Amp".to_string(), "Codex CLI".to_string()]),
+            "2 AI tools have updates (Amp, Codex CLI). Run `ai-cli-apps` for details."
+        );
+    }
+}
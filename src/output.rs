@@ -0,0 +1,120 @@
+use std::{
+    io::{self, IsTerminal},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is unset
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Settings {
+    quiet: bool,
+    verbose: bool,
+    terminal: bool,
+}
+
+/// Applies `--quiet`, `--verbose` and `--color` for the rest of the run.
+pub fn init(quiet: bool, verbose: bool, color: ColorChoice) {
+    let terminal = io::stdout().is_terminal();
+    let colorize = match color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            terminal && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+        }
+    };
+    colored::control::set_override(colorize);
+    let _ = SETTINGS.set(Settings {
+        quiet,
+        verbose,
+        terminal,
+    });
+}
+
+fn settings() -> Settings {
+    *SETTINGS.get_or_init(|| Settings {
+        quiet: false,
+        verbose: false,
+        terminal: io::stdout().is_terminal(),
+    })
+}
+
+/// Whether `--verbose` asked for details that are normally left out.
+pub fn verbose() -> bool {
+    settings().verbose
+}
+
+/// Writes generated output to stdout, treating a reader that stops early, as in
+/// `completions bash | head`, as success.
+pub fn write_stdout(write: impl FnOnce(&mut dyn io::Write) -> io::Result<()>) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    match write(&mut stdout).and_then(|()| io::Write::flush(&mut stdout)) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// Progress for a long-running step: an animated spinner on a terminal, plain lines on
+/// stderr when output is piped, and nothing at all with `--quiet`.
+pub struct Progress {
+    spinner: Option<ProgressBar>,
+    plain: bool,
+}
+
+impl Progress {
+    pub fn start(message: &str) -> Self {
+        let settings = settings();
+        let progress = Self {
+            spinner: None,
+            plain: !settings.quiet && !settings.terminal,
+        };
+        if settings.quiet {
+            return progress;
+        }
+        if progress.plain {
+            progress.set_message(message);
+            return progress;
+        }
+
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(
+            ProgressStyle::default_spinner()
+                .template("{spinner:.cyan} {msg}")
+                .unwrap(),
+        );
+        spinner.enable_steady_tick(Duration::from_millis(80));
+        spinner.set_message(message.to_string());
+        Self {
+            spinner: Some(spinner),
+            ..progress
+        }
+    }
+
+    pub fn set_message(&self, message: &str) {
+        if let Some(spinner) = &self.spinner {
+            spinner.set_message(message.to_string());
+        } else if self.plain {
+            eprintln!("{}", message);
+        }
+    }
+
+    pub fn finish(&self) {
+        if let Some(spinner) = &self.spinner {
+            spinner.finish_and_clear();
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+pub fn home_dir() -> Result<PathBuf> {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map(PathBuf::from)
+        .context("HOME environment variable not set")
+}
+
+fn xdg_dir(var: &str, fallback: &[&str]) -> Result<PathBuf> {
+    match std::env::var(var) {
+        Ok(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => Ok(fallback
+            .iter()
+            .fold(home_dir()?, |path, segment| path.join(segment))),
+    }
+}
+
+/// `$XDG_CONFIG_HOME`, defaulting to `~/.config`.
+pub fn config_home() -> Result<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", &[".config"])
+}
+
+/// `$XDG_DATA_HOME`, defaulting to `~/.local/share`.
+pub fn data_home() -> Result<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", &[".local", "share"])
+}
+
+/// `$XDG_CACHE_HOME`, defaulting to `~/.cache`.
+pub fn cache_home() -> Result<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", &[".cache"])
+}
+
+/// `$XDG_STATE_HOME/ai-cli-apps`, defaulting to `~/.local/state/ai-cli-apps`.
+pub fn state_dir() -> Result<PathBuf> {
+    Ok(xdg_dir("XDG_STATE_HOME", &[".local", "state"])?.join("ai-cli-apps"))
+}
+
+/// `$XDG_CONFIG_HOME/ai-cli-apps`, defaulting to `~/.config/ai-cli-apps`.
+pub fn config_dir() -> Result<PathBuf> {
+    Ok(config_home()?.join("ai-cli-apps"))
+}
+
+/// `$XDG_CACHE_HOME/ai-cli-apps`, defaulting to `~/.cache/ai-cli-apps`.
+pub fn cache_dir() -> Result<PathBuf> {
+    Ok(cache_home()?.join("ai-cli-apps"))
+}
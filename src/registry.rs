@@ -0,0 +1,155 @@
+use std::{collections::HashMap, fs, process::Command};
+
+use crate::{config::RegistryConfig, github, paths};
+
+pub const NPM_REGISTRY: &str = "https://registry.npmjs.org";
+pub const NPM_CDN: &str = "https://unpkg.com";
+
+/// Upstream endpoints after applying config overrides and `.npmrc`.
+#[derive(Debug, Clone)]
+pub struct Registries {
+    npm: String,
+    /// `@scope:registry=` entries from `.npmrc`, keyed by scope including the `@`.
+    npm_scopes: HashMap<String, String>,
+    /// Set when the registry comes from our own config, which npm itself doesn't read.
+    npm_override: Option<String>,
+    github_api: String,
+    homebrew_api: Option<String>,
+    npm_cdn: String,
+}
+
+impl Registries {
+    pub fn resolve(config: &RegistryConfig) -> Self {
+        Self::with_npmrc(config, read_npmrc())
+    }
+
+    fn with_npmrc(config: &RegistryConfig, npmrc: HashMap<String, String>) -> Self {
+        let npm = config
+            .npm
+            .clone()
+            .or_else(|| std::env::var("NPM_CONFIG_REGISTRY").ok())
+            .or_else(|| npmrc.get("registry").cloned())
+            .unwrap_or_else(|| NPM_REGISTRY.to_string());
+        let npm_scopes = npmrc
+            .into_iter()
+            .filter_map(|(key, url)| {
+                key.strip_suffix(":registry")
+                    .filter(|scope| scope.starts_with('@'))
+                    .map(|scope| (scope.to_string(), url))
+            })
+            .collect();
+
+        Self {
+            npm: trim_base(&npm),
+            npm_scopes,
+            npm_override: config.npm.as_deref().map(trim_base),
+            github_api: trim_base(config.github_api.as_deref().unwrap_or(github::API_BASE)),
+            homebrew_api: config.homebrew_api.as_deref().map(trim_base),
+            npm_cdn: trim_base(config.npm_cdn.as_deref().unwrap_or(NPM_CDN)),
+        }
+    }
+
+    /// Registry metadata URL for `package`, honouring scoped registries.
+    pub fn npm_package_url(&self, package: &str) -> String {
+        let registry = package
+            .split_once('/')
+            .and_then(|(scope, _)| self.npm_scopes.get(scope))
+            .map(|url| trim_base(url))
+            .unwrap_or_else(|| self.npm.clone());
+        format!("{}/{}", registry, package)
+    }
+
+    /// URL of `path` inside the published `package@version`.
+    pub fn npm_file_url(&self, package: &str, version: &str, path: &str) -> String {
+        format!("{}/{}@{}/{}", self.npm_cdn, package, version, path)
+    }
+
+    pub fn github_api(&self) -> &str {
+        &self.github_api
+    }
+
+    /// `npm` with `--registry` set when our config overrides it.
+    pub fn npm_command(&self) -> Command {
+        let mut command = Command::new("npm");
+        if let Some(registry) = &self.npm_override {
+            command.arg("--registry").arg(registry);
+        }
+        command
+    }
+
+    /// `brew` pointed at the configured Homebrew API mirror, if any.
+    pub fn brew_command(&self) -> Command {
+        let mut command = Command::new("brew");
+        if let Some(api) = &self.homebrew_api {
+            command.env("HOMEBREW_API_DOMAIN", api);
+        }
+        command
+    }
+}
+
+fn trim_base(url: &str) -> String {
+    url.trim_end_matches('/').to_string()
+}
+
+/// `key=value` pairs from `~/.npmrc`, ignoring comments.
+fn read_npmrc() -> HashMap<String, String> {
+    let path = std::env::var("NPM_CONFIG_USERCONFIG")
+        .map(Into::into)
+        .or_else(|_| paths::home_dir().map(|home| home.join(".npmrc")));
+    path.ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|content| parse_npmrc(&content))
+        .unwrap_or_default()
+}
+
+fn parse_npmrc(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with(';'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Registries, parse_npmrc};
+    use crate::config::RegistryConfig;
+
+    #[test]
+    fn it_resolves_scoped_and_default_npm_registries() {
+        let npmrc = parse_npmrc(
+            "; comment\nregistry=https://npm.mirror.example/\n\
+             @kilocode:registry=https://npm.kilo.example/\n\
+             //npm.kilo.example/:_authToken=secret\n",
+        );
+        let registries = Registries::with_npmrc(&RegistryConfig::default(), npmrc.clone());
+
+        assert_eq!(
+            registries.npm_package_url("cline"),
+            "https://npm.mirror.example/cline"
+        );
+        assert_eq!(
+            registries.npm_package_url("@kilocode/cli"),
+            "https://npm.kilo.example/@kilocode/cli"
+        );
+        assert!(registries.npm_override.is_none());
+
+        let registries = Registries::with_npmrc(
+            &RegistryConfig {
+                npm: Some("https://verdaccio.corp/".to_string()),
+                ..RegistryConfig::default()
+            },
+            npmrc,
+        );
+        assert_eq!(
+            registries.npm_package_url("cline"),
+            "https://verdaccio.corp/cline"
+        );
+        assert_eq!(
+            registries.npm_override.as_deref(),
+            Some("https://verdaccio.corp")
+        );
+    }
+}
@@ -0,0 +1,535 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use colored::*;
+
+use crate::{actions::require_tool, cli::ScheduledJob, paths, tools};
+
+/// Name of the systemd user service and timer.
+const UNIT: &str = "ai-cli-apps-upgrade";
+/// Trailing comment that identifies our crontab line.
+const CRON_MARKER: &str = "# ai-cli-apps schedule";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl Frequency {
+    /// `OnCalendar=` shorthand; cron takes the same word prefixed with `@`.
+    fn name(self) -> &'static str {
+        match self {
+            Frequency::Hourly => "hourly",
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+        }
+    }
+}
+
+/// Where scheduled runs append their output.
+pub fn log_path() -> Result<PathBuf> {
+    Ok(paths::state_dir()?.join("schedule.log"))
+}
+
+/// Installs the scheduled upgrade, replacing any existing one.
+pub fn enable(frequency: Frequency, job: &ScheduledJob) -> Result<()> {
+    let catalog = tools::catalog();
+    for name in &job.tools {
+        require_tool(&catalog, name)?;
+    }
+
+    let exe = std::env::current_exe().context("Failed to locate the ai-cli-apps binary")?;
+    let mut command = vec![exe.to_string_lossy().into_owned()];
+    command.extend(run_args(job));
+    // Timers and cron start with a minimal PATH, which wouldn't find npm or brew.
+    let path = std::env::var("PATH").unwrap_or_default();
+
+    // Check before removing anything, so a system without either keeps its schedule.
+    let systemd = systemd_available();
+    if !systemd && !cron_available() {
+        anyhow::bail!("Neither systemd user services nor crontab are available on this system");
+    }
+
+    remove_schedule()?;
+    if systemd {
+        enable_systemd(frequency, &command, &path)?;
+        println!(
+            "{} Enabled {} upgrades via systemd timer {}.timer",
+            "✓".green(),
+            frequency.name(),
+            UNIT
+        );
+    } else {
+        enable_cron(frequency, &command, &path)?;
+        println!(
+            "{} Enabled {} upgrades via crontab",
+            "✓".green(),
+            frequency.name()
+        );
+    }
+    println!("  Output is appended to {}", log_path()?.display());
+    Ok(())
+}
+
+pub fn disable() -> Result<()> {
+    if remove_schedule()? {
+        println!("{} Scheduled upgrades disabled", "✓".green());
+    } else {
+        println!("{}", "No scheduled upgrade is enabled.".yellow());
+    }
+    Ok(())
+}
+
+pub fn status() -> Result<()> {
+    let timer = systemd_unit_path("timer")?;
+    if timer.exists() {
+        let calendar = unit_setting(&timer, "OnCalendar").unwrap_or_default();
+        println!(
+            "{} Enabled via systemd timer {}.timer ({})",
+            "✓".green(),
+            UNIT,
+            calendar
+        );
+        let service = systemd_unit_path("service")?;
+        if let Some(command) = unit_setting(&service, "ExecStart") {
+            println!("  Command:  {}", command);
+        }
+        if let Some(next) = systemctl_output(&[
+            "show",
+            &format!("{}.timer", UNIT),
+            "--property=NextElapseUSecRealtime",
+            "--value",
+        ]) {
+            println!("  Next run: {}", next);
+        }
+    } else if let Some(line) = cron_lines()
+        .into_iter()
+        .find(|line| line.contains(CRON_MARKER))
+    {
+        let line = line.trim_end_matches(CRON_MARKER).trim();
+        let (when, command) = line.split_once(' ').unwrap_or((line, ""));
+        println!("{} Enabled via crontab ({})", "✓".green(), when);
+        println!("  Command:  {}", command);
+    } else {
+        println!(
+            "{} No scheduled upgrade. Enable one with `ai-cli-apps schedule enable`.",
+            "!".yellow()
+        );
+        return Ok(());
+    }
+
+    let log = log_path()?;
+    let content = fs::read_to_string(&log).unwrap_or_default();
+    match content.lines().rev().find(|line| line.starts_with("== ")) {
+        Some(last) => println!("  Last run: {}", last.trim_start_matches("== ")),
+        None => println!("  Last run: never"),
+    }
+    println!("  Log:      {}", log.display());
+    Ok(())
+}
+
+/// Runs `upgrade` for the job with its output appended to the log; this is what the timer
+/// or cron entry invokes.
+pub fn run(job: &ScheduledJob) -> Result<()> {
+    let path = log_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let args = upgrade_args(job);
+    let started = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %z");
+    writeln!(
+        log,
+        "\n== {} started: ai-cli-apps {}",
+        started,
+        args.join(" ")
+    )?;
+    let status = Command::new(std::env::current_exe()?)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log.try_clone()?)
+        .status()
+        .context("Failed to run ai-cli-apps upgrade")?;
+
+    let finished = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %z");
+    let result = if status.success() {
+        "succeeded".to_string()
+    } else {
+        format!("failed ({})", status)
+    };
+    writeln!(log, "== {} {}", finished, result)?;
+
+    if !status.success() {
+        anyhow::bail!("Scheduled upgrade failed, see {}", path.display());
+    }
+    Ok(())
+}
+
+/// `--policy <name>` when the job sets one; otherwise the config decides per tool.
+fn policy_args(job: &ScheduledJob) -> Vec<String> {
+    job.policy
+        .and_then(|policy| policy.to_possible_value())
+        .map(|value| vec!["--policy".to_string(), value.get_name().to_string()])
+        .unwrap_or_default()
+}
+
+/// Arguments after the binary for the `upgrade` that `schedule run` starts.
+fn upgrade_args(job: &ScheduledJob) -> Vec<String> {
+    let mut args = vec!["upgrade".to_string()];
+    if job.tools.is_empty() {
+        args.push("--all".to_string());
+    } else {
+        args.extend(job.tools.iter().cloned());
+    }
+    // An unattended run never upgrades more freely than `upgrade --all` would.
+    args.push("--respect-policy".to_string());
+    args.extend(policy_args(job));
+    args.extend(["--quiet".to_string(), "--color=never".to_string()]);
+    args
+}
+
+/// Arguments after the binary for the scheduled `schedule run` invocation.
+fn run_args(job: &ScheduledJob) -> Vec<String> {
+    let mut args = vec!["schedule".to_string(), "run".to_string()];
+    args.extend(policy_args(job));
+    args.extend(job.tools.iter().cloned());
+    args
+}
+
+/// Removes our timer and crontab line, returning whether either existed.
+fn remove_schedule() -> Result<bool> {
+    let mut removed = false;
+
+    let timer = systemd_unit_path("timer")?;
+    let service = systemd_unit_path("service")?;
+    if timer.exists() || service.exists() {
+        systemctl_output(&["disable", "--now", &format!("{}.timer", UNIT)]);
+        for path in [&timer, &service] {
+            if path.exists() {
+                fs::remove_file(path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+        systemctl_output(&["daemon-reload"]);
+        removed = true;
+    }
+
+    let lines = cron_lines();
+    if lines.iter().any(|line| line.contains(CRON_MARKER)) {
+        let kept: Vec<String> = lines
+            .into_iter()
+            .filter(|line| !line.contains(CRON_MARKER))
+            .collect();
+        write_crontab(&kept)?;
+        removed = true;
+    }
+    Ok(removed)
+}
+
+fn systemd_available() -> bool {
+    cfg!(target_os = "linux") && systemctl_output(&["show-environment"]).is_some()
+}
+
+fn cron_available() -> bool {
+    Command::new("crontab")
+        .arg("-l")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+fn systemd_unit_path(kind: &str) -> Result<PathBuf> {
+    Ok(paths::config_home()?
+        .join("systemd")
+        .join("user")
+        .join(format!("{}.{}", UNIT, kind)))
+}
+
+/// Trimmed stdout of `systemctl --user <args>`, or `None` if it couldn't run or failed.
+fn systemctl_output(args: &[&str]) -> Option<String> {
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn enable_systemd(frequency: Frequency, command: &[String], path: &str) -> Result<()> {
+    let service = format!(
+        "[Unit]\n\
+         Description=Upgrade AI CLI tools (ai-cli-apps)\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         Environment={}\n\
+         ExecStart={}\n",
+        systemd_quote(&format!("PATH={}", path)),
+        command
+            .iter()
+            // ExecStart= also expands $VARIABLES, which Environment= leaves alone.
+            .map(|arg| systemd_quote(&arg.replace('$', "$$")))
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    let timer = format!(
+        "[Unit]\n\
+         Description=Upgrade AI CLI tools (ai-cli-apps), {}\n\
+         \n\
+         [Timer]\n\
+         OnCalendar={}\n\
+         Persistent=true\n\
+         RandomizedDelaySec=15min\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n",
+        frequency.name(),
+        frequency.name()
+    );
+
+    for (kind, content) in [("service", service), ("timer", timer)] {
+        let path = systemd_unit_path(kind)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    systemctl_output(&["daemon-reload"]).context("`systemctl --user daemon-reload` failed")?;
+    systemctl_output(&["enable", "--now", &format!("{}.timer", UNIT)])
+        .with_context(|| format!("Failed to enable {}.timer", UNIT))?;
+    Ok(())
+}
+
+fn enable_cron(frequency: Frequency, command: &[String], path: &str) -> Result<()> {
+    let mut lines = cron_lines();
+    lines.push(cron_line(frequency, command, path));
+    write_crontab(&lines)
+}
+
+/// Our crontab entry, ending in [`CRON_MARKER`] so it can be found and removed again.
+fn cron_line(frequency: Frequency, command: &[String], path: &str) -> String {
+    let command = command
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>();
+    format!(
+        "@{} PATH={} {} >/dev/null 2>&1 {}",
+        frequency.name(),
+        shell_quote(path),
+        command.join(" "),
+        CRON_MARKER
+    )
+    // cron turns unescaped % into newlines.
+    .replace('%', "\\%")
+}
+
+/// The current user's crontab, empty if there is none.
+fn cron_lines() -> Vec<String> {
+    Command::new("crontab")
+        .arg("-l")
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn write_crontab(lines: &[String]) -> Result<()> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to run crontab")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let mut content = lines.join("\n");
+        content.push('\n');
+        stdin.write_all(content.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        anyhow::bail!("crontab rejected the updated schedule");
+    }
+    Ok(())
+}
+
+/// The value of `key=` in a unit file we wrote.
+fn unit_setting(path: &PathBuf, key: &str) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix(&format!("{}=", key)))
+        .map(str::to_string)
+}
+
+/// Whether `arg` only has characters that sh and systemd both take literally.
+fn is_plain(arg: &str) -> bool {
+    !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-=:,+@".contains(c))
+}
+
+/// Single-quotes `arg` for sh unless it's plain.
+fn shell_quote(arg: &str) -> String {
+    if is_plain(arg) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Double-quotes `arg` for a systemd unit setting unless it's plain. systemd has no `'\''`
+/// trick, so backslashes and double quotes are escaped, and `%` is doubled so it isn't read
+/// as a specifier.
+fn systemd_quote(arg: &str) -> String {
+    if is_plain(arg) {
+        arg.to_string()
+    } else {
+        let escaped = arg
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%");
+        format!("\"{}\"", escaped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CRON_MARKER, Frequency, cron_line, run_args, shell_quote, systemd_quote, upgrade_args,
+    };
+    use crate::{
+        actions::compares_versions,
+        cli::{self, Cli, Commands, FrequencyArgs, ScheduledJob},
+        versions::UpgradePolicy,
+    };
+    use clap::FromArgMatches;
+
+    fn job(tools: &[&str], policy: Option<UpgradePolicy>) -> ScheduledJob {
+        ScheduledJob {
+            tools: tools.iter().map(|name| name.to_string()).collect(),
+            policy,
+        }
+    }
+
+    #[test]
+    fn it_passes_a_policy_only_when_the_job_sets_one() {
+        assert_eq!(run_args(&job(&[], None)), ["schedule", "run"]);
+        assert_eq!(
+            run_args(&job(&["claude", "codex"], Some(UpgradePolicy::Minor))),
+            ["schedule", "run", "--policy", "minor", "claude", "codex"]
+        );
+        assert_eq!(
+            upgrade_args(&job(&[], None)),
+            [
+                "upgrade",
+                "--all",
+                "--respect-policy",
+                "--quiet",
+                "--color=never"
+            ]
+        );
+        assert_eq!(
+            upgrade_args(&job(&["amp"], Some(UpgradePolicy::Patch))),
+            [
+                "upgrade",
+                "amp",
+                "--respect-policy",
+                "--policy",
+                "patch",
+                "--quiet",
+                "--color=never"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_applies_policies_to_scheduled_upgrades_of_named_tools() {
+        let args = upgrade_args(&job(&["claude", "codex"], None));
+        let matches = cli::command()
+            .try_get_matches_from(std::iter::once("ai-cli-apps".to_string()).chain(args))
+            .unwrap();
+        let Some(Commands::Upgrade {
+            selection,
+            policy,
+            respect_policy,
+        }) = Cli::from_arg_matches(&matches).unwrap().command
+        else {
+            panic!("expected an upgrade command");
+        };
+        assert_eq!(selection.tools, ["claude", "codex"]);
+        assert!(compares_versions(&selection, policy, respect_policy));
+    }
+
+    #[test]
+    fn it_quotes_for_sh_and_systemd_separately() {
+        assert_eq!(shell_quote("/usr/bin/ai-cli-apps"), "/usr/bin/ai-cli-apps");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("/tmp/it's here"), r"'/tmp/it'\''s here'");
+
+        assert_eq!(systemd_quote("--policy"), "--policy");
+        assert_eq!(systemd_quote("/tmp/it's here"), r#""/tmp/it's here""#);
+        assert_eq!(systemd_quote(r#"C:\a "b" 100%"#), r#""C:\\a \"b\" 100%%""#);
+    }
+
+    #[test]
+    fn it_marks_the_cron_line_and_escapes_percent_signs() {
+        let command = [
+            "/opt/ai cli/ai-cli-apps".to_string(),
+            "schedule".to_string(),
+        ];
+        let line = cron_line(Frequency::Weekly, &command, "/usr/bin:/50%/bin");
+        assert_eq!(
+            line,
+            format!(
+                r"@weekly PATH='/usr/bin:/50\%/bin' '/opt/ai cli/ai-cli-apps' schedule >/dev/null 2>&1 {}",
+                CRON_MARKER
+            )
+        );
+        assert!(line.ends_with(CRON_MARKER));
+    }
+
+    #[test]
+    fn it_runs_daily_unless_another_frequency_is_given() {
+        let frequency = |hourly, weekly| {
+            FrequencyArgs {
+                hourly,
+                daily: false,
+                weekly,
+            }
+            .frequency()
+        };
+        assert_eq!(frequency(false, false), Frequency::Daily);
+        assert_eq!(frequency(true, false), Frequency::Hourly);
+        assert_eq!(frequency(false, true), Frequency::Weekly);
+        assert_eq!(Frequency::Hourly.name(), "hourly");
+        assert_eq!(Frequency::Weekly.name(), "weekly");
+    }
+}
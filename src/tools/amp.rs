@@ -1,21 +1,28 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Amp",
         InstallMethod::Amp("https://ampcode.com/install.sh".to_string()),
         vec!["amp".to_string(), "--version".to_string()],
+        parse_version,
     )
+    .with_install_method(InstallMethod::Npm("@sourcegraph/amp".to_string()))
     .with_binary_name("amp")
+    .with_xdg_dirs("amp")
+    .with_description("Agentic coding tool for the terminal and editors")
+    .with_vendor("Sourcegraph")
+    .with_homepage("https://ampcode.com")
+    .with_docs_url("https://ampcode.com/manual")
+    .with_license("Proprietary; pay-as-you-go credits, ad-supported free tier")
+    .with_runtime("Node.js 20+ (npm installs only)")
+    .with_auth_env("AMP_API_KEY")
+    .with_xdg_auth_file("amp/secrets.json")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("amp", &["--version"]).and_then(|s| {
-        s.lines()
-            .next()
-            .map(|l| l.split(" (released").next().unwrap_or(l).to_string())
-    });
-    ToolVersion::new("Amp")
-        .with_installed(installed)
-        .with_identifier("amp")
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()
+        .map(|l| l.split(" (released").next().unwrap_or(l).to_string())
 }
@@ -1,21 +1,32 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
+
+/// GCS bucket holding the bootstrap script and the per-channel version manifests.
+pub const RELEASES_URL: &str = "https://storage.googleapis.com/claude-code-dist-86c565f3-f756-42ad-8dfa-d59b1c096819/claude-code-releases";
 
 pub fn definition() -> Tool {
     Tool::new(
         "Claude Code",
-        InstallMethod::Bootstrap(
-            "https://storage.googleapis.com/claude-code-dist-86c565f3-f756-42ad-8dfa-d59b1c096819/claude-code-releases/bootstrap.sh"
-                .to_string(),
-        ),
+        InstallMethod::Bootstrap(format!("{}/bootstrap.sh", RELEASES_URL)),
         vec!["claude".to_string(), "--version".to_string()],
+        parse_version,
     )
+    .with_install_method(InstallMethod::Npm("@anthropic-ai/claude-code".to_string()))
     .with_binary_name("claude")
+    .with_bootstrap_channels()
+    .with_description("Agentic coding assistant that works in your terminal")
+    .with_vendor("Anthropic")
+    .with_homepage("https://www.claude.com/product/claude-code")
+    .with_docs_url("https://code.claude.com/docs")
+    .with_license("Proprietary; needs a Claude subscription or API key")
+    .with_runtime("Node.js 18+ (npm installs only)")
+    .with_auth_env("ANTHROPIC_API_KEY")
+    .with_auth_env("CLAUDE_CODE_OAUTH_TOKEN")
+    .with_auth_file(".claude/.credentials.json")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("claude", &["--version"])
-        .and_then(|s| s.lines().next().map(|l| l.replace(" (Claude Code)", "")));
-    ToolVersion::new("Claude Code")
-        .with_installed(installed)
-        .with_identifier("claude")
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()
+        .map(|l| l.replace(" (Claude Code)", ""))
 }
@@ -1,31 +1,33 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Cline CLI",
         InstallMethod::Npm("cline".to_string()),
         vec!["cline".to_string(), "version".to_string()],
+        parse_version,
     )
     .with_binary_name("cline")
+    .with_description("Cline's autonomous coding agent for the terminal")
+    .with_vendor("Cline Bot")
+    .with_homepage("https://cline.bot")
+    .with_docs_url("https://docs.cline.bot/cline-cli/overview")
+    .with_license("Apache-2.0; bring your own API key")
+    .with_runtime("Node.js 20+")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("cline", &["version"]).and_then(|output| {
-        output
-            .lines()
-            .find(|line| line.contains("Cline CLI Version:"))
-            .and_then(|line| {
-                line.split_whitespace().nth(3).map(|v| {
-                    let core = output
-                        .lines()
-                        .find(|l| l.contains("Cline Core Version:"))
-                        .and_then(|l| l.split_whitespace().nth(3))
-                        .unwrap_or("");
-                    format!("{} (Core: {})", v, core)
-                })
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find(|line| line.contains("Cline CLI Version:"))
+        .and_then(|line| {
+            line.split_whitespace().nth(3).map(|v| {
+                let core = output
+                    .lines()
+                    .find(|l| l.contains("Cline Core Version:"))
+                    .and_then(|l| l.split_whitespace().nth(3))
+                    .unwrap_or("");
+                format!("{} (Core: {})", v, core)
             })
-    });
-    ToolVersion::new("Cline CLI")
-        .with_installed(installed)
-        .with_identifier("cline")
+        })
 }
@@ -1,18 +1,24 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Codex CLI",
         InstallMethod::Brew("codex".to_string()),
         vec!["codex".to_string(), "--version".to_string()],
+        parse_version,
     )
+    .with_install_method(InstallMethod::Npm("@openai/codex".to_string()))
     .with_binary_name("codex")
+    .with_description("Lightweight coding agent that runs in your terminal")
+    .with_vendor("OpenAI")
+    .with_homepage("https://github.com/openai/codex")
+    .with_docs_url("https://developers.openai.com/codex/cli/")
+    .with_license("Apache-2.0; needs a ChatGPT plan or OpenAI API key")
+    .with_runtime("Node.js 16+ (npm installs only)")
+    .with_auth_env("OPENAI_API_KEY")
+    .with_auth_file(".codex/auth.json")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("codex", &["--version"])
-        .map(|v| v.trim_start_matches("codex-cli").trim().to_string());
-    ToolVersion::new("Codex CLI")
-        .with_installed(installed)
-        .with_identifier("codex")
+fn parse_version(output: &str) -> Option<String> {
+    Some(output.trim_start_matches("codex-cli").trim().to_string())
 }
@@ -1,18 +1,24 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Copilot CLI",
         InstallMethod::Npm("@github/copilot".to_string()),
         vec!["copilot".to_string(), "--version".to_string()],
+        parse_version,
     )
     .with_binary_name("copilot")
+    .with_description("GitHub Copilot's coding agent for the terminal")
+    .with_vendor("GitHub")
+    .with_homepage("https://github.com/features/copilot/cli")
+    .with_docs_url("https://docs.github.com/en/copilot/concepts/agents/about-copilot-cli")
+    .with_license("Proprietary; needs a GitHub Copilot subscription")
+    .with_runtime("Node.js 22+")
+    .with_auth_env("COPILOT_GITHUB_TOKEN")
+    .with_auth_env("GH_TOKEN")
+    .with_auth_env("GITHUB_TOKEN")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("copilot", &["--version"])
-        .and_then(|s| s.lines().next().map(|l| l.to_string()));
-    ToolVersion::new("Copilot CLI")
-        .with_installed(installed)
-        .with_identifier("copilot")
+fn parse_version(output: &str) -> Option<String> {
+    output.lines().next().map(|l| l.to_string())
 }
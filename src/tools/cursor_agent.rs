@@ -1,18 +1,21 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Cursor CLI",
         InstallMethod::Bootstrap("https://cursor.com/install".to_string()),
         vec!["cursor-agent".to_string(), "--version".to_string()],
+        parse_version,
     )
     .with_binary_name("cursor-agent")
+    .with_description("Cursor's coding agent for the terminal")
+    .with_vendor("Anysphere")
+    .with_homepage("https://cursor.com/cli")
+    .with_docs_url("https://cursor.com/docs/cli/overview")
+    .with_license("Proprietary; needs a Cursor account")
+    .with_auth_env("CURSOR_API_KEY")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("cursor-agent", &["--version"])
-        .and_then(|output| output.lines().next().map(|line| line.to_string()));
-    ToolVersion::new("Cursor CLI")
-        .with_installed(installed)
-        .with_identifier("cursor-agent")
+fn parse_version(output: &str) -> Option<String> {
+    output.lines().next().map(|line| line.to_string())
 }
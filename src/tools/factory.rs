@@ -1,39 +1,42 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Factory CLI",
         InstallMethod::Bootstrap("https://app.factory.ai/cli".to_string()),
         vec!["droid".to_string(), "--version".to_string()],
+        parse_version,
     )
     .with_binary_name("droid")
     .with_config_dir(".factory")
+    .with_description("Factory's Droid software development agent for the terminal")
+    .with_vendor("Factory")
+    .with_homepage("https://factory.ai")
+    .with_docs_url("https://docs.factory.ai/cli/getting-started/overview")
+    .with_license("Proprietary; needs a Factory account")
+    .with_auth_env("FACTORY_API_KEY")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("droid", &["--version"]).and_then(|output| {
-        output
-            .lines()
-            .filter_map(|line| {
-                let trimmed = line.trim();
-                if trimmed.is_empty() {
-                    return None;
-                }
+/// The last line that is a bare version, optionally prefixed with `v`.
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                return None;
+            }
 
-                let version_candidate = trimmed.trim_start_matches('v');
-                if !version_candidate.is_empty()
-                    && version_candidate
-                        .chars()
-                        .all(|c| c.is_ascii_digit() || c == '.')
-                {
-                    Some(version_candidate.to_string())
-                } else {
-                    None
-                }
-            })
-            .next_back()
-    });
-    ToolVersion::new("Factory CLI")
-        .with_installed(installed)
-        .with_identifier("droid")
+            let version_candidate = trimmed.trim_start_matches('v');
+            if !version_candidate.is_empty()
+                && version_candidate
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '.')
+            {
+                Some(version_candidate.to_string())
+            } else {
+                None
+            }
+        })
+        .next_back()
 }
@@ -1,17 +1,26 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Gemini CLI",
         InstallMethod::Brew("gemini-cli".to_string()),
         vec!["gemini".to_string(), "--version".to_string()],
+        parse_version,
     )
+    .with_install_method(InstallMethod::Npm("@google/gemini-cli".to_string()))
     .with_binary_name("gemini")
+    .with_description("Open-source AI agent that brings Gemini into your terminal")
+    .with_vendor("Google")
+    .with_homepage("https://github.com/google-gemini/gemini-cli")
+    .with_docs_url("https://docs.cloud.google.com/gemini/docs/codeassist/gemini-cli")
+    .with_license("Apache-2.0; free tier with a Google account")
+    .with_runtime("Node.js 20+")
+    .with_auth_env("GEMINI_API_KEY")
+    .with_auth_env("GOOGLE_API_KEY")
+    .with_auth_env("GOOGLE_APPLICATION_CREDENTIALS")
+    .with_auth_file(".gemini/oauth_creds.json")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("gemini", &["--version"]);
-    ToolVersion::new("Gemini CLI")
-        .with_installed(installed)
-        .with_identifier("gemini")
+fn parse_version(output: &str) -> Option<String> {
+    Some(output.to_string())
 }
@@ -1,17 +1,21 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "Kilo Code CLI",
         InstallMethod::Npm("@kilocode/cli".to_string()),
         vec!["kilo".to_string(), "--version".to_string()],
+        parse_version,
     )
     .with_binary_name("kilo")
+    .with_description("Open-source coding agent with a choice of model providers")
+    .with_vendor("Kilo Code")
+    .with_homepage("https://kilocode.ai")
+    .with_docs_url("https://kilocode.ai/docs/cli")
+    .with_license("Apache-2.0; bring your own API key or buy Kilo credits")
+    .with_runtime("Node.js 20+")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("kilo", &["--version"]);
-    ToolVersion::new("Kilo Code CLI")
-        .with_installed(installed)
-        .with_identifier("kilo")
+fn parse_version(output: &str) -> Option<String> {
+    Some(output.to_string())
 }
@@ -9,22 +9,35 @@ mod gemini;
 mod kilo;
 mod opencode;
 
-use std::process::Command;
-
-pub use amp::{definition as amp_tool, installed_version as amp_installed_version};
-pub use claude::{definition as claude_tool, installed_version as claude_installed_version};
-pub use cline::{definition as cline_tool, installed_version as cline_installed_version};
-pub use codex::{definition as codex_tool, installed_version as codex_installed_version};
-pub use copilot::{definition as copilot_tool, installed_version as copilot_installed_version};
-pub use cursor_agent::{
-    definition as cursor_agent_tool, installed_version as cursor_agent_installed_version,
+use std::{
+    fmt,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
 };
-pub use factory::{
-    definition as factory_cli_tool, installed_version as factory_cli_installed_version,
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    paths,
+    versions::{HeldBack, LookupFailure},
 };
-pub use gemini::{definition as gemini_tool, installed_version as gemini_installed_version};
-pub use kilo::{definition as kilo_tool, installed_version as kilo_installed_version};
-pub use opencode::{definition as opencode_tool, installed_version as opencode_installed_version};
+
+pub use amp::definition as amp_tool;
+pub use claude::{RELEASES_URL as CLAUDE_RELEASES_URL, definition as claude_tool};
+pub use cline::definition as cline_tool;
+pub use codex::definition as codex_tool;
+pub use copilot::definition as copilot_tool;
+pub use cursor_agent::definition as cursor_agent_tool;
+pub use factory::definition as factory_cli_tool;
+pub use gemini::definition as gemini_tool;
+pub use kilo::definition as kilo_tool;
+pub use opencode::definition as opencode_tool;
 
 #[derive(Debug, Clone)]
 pub enum InstallMethod {
@@ -34,28 +47,120 @@ pub enum InstallMethod {
     Brew(String),
 }
 
+impl InstallMethod {
+    pub fn kind(&self) -> MethodKind {
+        match self {
+            InstallMethod::Npm(_) => MethodKind::Npm,
+            InstallMethod::Bootstrap(_) | InstallMethod::Amp(_) => MethodKind::Bootstrap,
+            InstallMethod::Brew(_) => MethodKind::Brew,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            InstallMethod::Npm(pkg) => format!("npm: {}", pkg),
+            InstallMethod::Bootstrap(_) => "bootstrap".to_string(),
+            InstallMethod::Brew(formula) => format!("brew: {}", formula),
+            InstallMethod::Amp(_) => "amp installer".to_string(),
+        }
+    }
+}
+
+/// Release channel used when none is configured; npm's default dist-tag and Claude's default
+/// bootstrap channel.
+pub const DEFAULT_CHANNEL: &str = "latest";
+
+/// The user-facing name of an install method, as accepted by `--via` and the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MethodKind {
+    Bootstrap,
+    Brew,
+    Npm,
+}
+
+impl MethodKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MethodKind::Bootstrap => "bootstrap",
+            MethodKind::Brew => "brew",
+            MethodKind::Npm => "npm",
+        }
+    }
+}
+
+impl fmt::Display for MethodKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Tool {
     pub name: String,
-    pub install_method: InstallMethod,
+    /// Candidate install methods, in the vendor's recommended order.
+    pub install_methods: Vec<InstallMethod>,
     pub check_command: Vec<String>,
+    /// Extracts the version from `check_command`'s output.
+    pub parse_version: fn(&str) -> Option<String>,
     pub binary_name: Option<String>,
     pub config_dirs: Vec<String>,
+    /// Name of the tool's directories under the XDG config, data and cache homes.
+    pub xdg_dirs: Option<String>,
     pub extra_binary_paths: Vec<String>,
+    /// Whether the bootstrap script takes a release channel (e.g. `stable`) as its argument.
+    pub bootstrap_channels: bool,
+    /// One-line summary shown by `info` and the install menu.
+    pub description: Option<String>,
+    pub vendor: Option<String>,
+    pub homepage: Option<String>,
+    pub docs_url: Option<String>,
+    /// License and what it costs to use, e.g. `Apache-2.0; free with a Google account`.
+    pub license: Option<String>,
+    /// What has to be installed first, e.g. `Node.js 20+`.
+    pub runtime: Option<String>,
+    /// Environment variables the tool authenticates with.
+    pub auth_env: Vec<String>,
+    /// Files, relative to the home directory, where the tool stores a login.
+    pub auth_files: Vec<String>,
+    /// Login files relative to `$XDG_DATA_HOME`, for tools that follow it.
+    pub xdg_auth_files: Vec<String>,
 }
 
 impl Tool {
-    pub fn new(name: &str, install_method: InstallMethod, check_command: Vec<String>) -> Self {
+    pub fn new(
+        name: &str,
+        install_method: InstallMethod,
+        check_command: Vec<String>,
+        parse_version: fn(&str) -> Option<String>,
+    ) -> Self {
         Self {
             name: name.to_string(),
-            install_method,
+            install_methods: vec![install_method],
             check_command,
+            parse_version,
             binary_name: None,
             config_dirs: Vec::new(),
+            xdg_dirs: None,
             extra_binary_paths: Vec::new(),
+            bootstrap_channels: false,
+            description: None,
+            vendor: None,
+            homepage: None,
+            docs_url: None,
+            license: None,
+            runtime: None,
+            auth_env: Vec::new(),
+            auth_files: Vec::new(),
+            xdg_auth_files: Vec::new(),
         }
     }
 
+    pub fn with_install_method(mut self, install_method: InstallMethod) -> Self {
+        self.install_methods.push(install_method);
+        self
+    }
+
     pub fn with_binary_name(mut self, binary_name: &str) -> Self {
         self.binary_name = Some(binary_name.to_string());
         self
@@ -66,21 +171,182 @@ impl Tool {
         self
     }
 
+    pub fn with_xdg_dirs(mut self, name: &str) -> Self {
+        self.xdg_dirs = Some(name.to_string());
+        self
+    }
+
     pub fn with_extra_binary_path(mut self, path: &str) -> Self {
         self.extra_binary_paths.push(path.to_string());
         self
     }
 
-    pub fn is_installed(&self) -> bool {
-        if self.check_command.is_empty() {
-            return false;
+    pub fn with_bootstrap_channels(mut self) -> Self {
+        self.bootstrap_channels = true;
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn with_vendor(mut self, vendor: &str) -> Self {
+        self.vendor = Some(vendor.to_string());
+        self
+    }
+
+    pub fn with_homepage(mut self, url: &str) -> Self {
+        self.homepage = Some(url.to_string());
+        self
+    }
+
+    pub fn with_docs_url(mut self, url: &str) -> Self {
+        self.docs_url = Some(url.to_string());
+        self
+    }
+
+    pub fn with_license(mut self, license: &str) -> Self {
+        self.license = Some(license.to_string());
+        self
+    }
+
+    pub fn with_runtime(mut self, runtime: &str) -> Self {
+        self.runtime = Some(runtime.to_string());
+        self
+    }
+
+    pub fn with_auth_env(mut self, var: &str) -> Self {
+        self.auth_env.push(var.to_string());
+        self
+    }
+
+    pub fn with_auth_file(mut self, path: &str) -> Self {
+        self.auth_files.push(path.to_string());
+        self
+    }
+
+    pub fn with_xdg_auth_file(mut self, path: &str) -> Self {
+        self.xdg_auth_files.push(path.to_string());
+        self
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self
+                .binary_name
+                .as_ref()
+                .map(|b| b.eq_ignore_ascii_case(name))
+                .unwrap_or(false)
+    }
+
+    pub fn install_method(&self, kind: MethodKind) -> Option<&InstallMethod> {
+        self.install_methods.iter().find(|m| m.kind() == kind)
+    }
+
+    /// The first supported method from `preference`, or the vendor default.
+    pub fn preferred_install_method(&self, preference: &[MethodKind]) -> &InstallMethod {
+        preference
+            .iter()
+            .find_map(|kind| self.install_method(*kind))
+            .unwrap_or(&self.install_methods[0])
+    }
+
+    pub fn available_methods(&self) -> String {
+        self.install_methods
+            .iter()
+            .map(|m| m.kind().as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Works out which install method the copy on `PATH` came from by resolving its binary.
+    /// `None` when it isn't on `PATH` or was installed some way the tool doesn't support.
+    pub fn detect_install_method(&self) -> Option<&InstallMethod> {
+        let binary_path = self.binary_path()?;
+        let resolved = binary_path.canonicalize().unwrap_or(binary_path);
+        self.install_method(classify_install_path(&resolved))
+    }
+
+    pub fn binary_path(&self) -> Option<PathBuf> {
+        self.binary_paths().into_iter().next()
+    }
+
+    /// Every copy of the binary: each match on `PATH` in order, then the extra install
+    /// locations that exist. The first one is what runs.
+    pub fn binary_paths(&self) -> Vec<PathBuf> {
+        let Some(binary_name) = self
+            .binary_name
+            .as_deref()
+            .or_else(|| self.check_command.first().map(String::as_str))
+        else {
+            return Vec::new();
+        };
+
+        let mut found: Vec<PathBuf> = std::env::var_os("PATH")
+            .map(|path| {
+                std::env::split_paths(&path)
+                    .map(|dir| dir.join(binary_name))
+                    .filter(|candidate| candidate.is_file())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if let Ok(home) = std::env::var("HOME") {
+            for extra in &self.extra_binary_paths {
+                let candidate = Path::new(&home).join(extra);
+                if candidate.is_file() && !found.contains(&candidate) {
+                    found.push(candidate);
+                }
+            }
         }
+        found
+    }
 
-        Command::new(&self.check_command[0])
-            .args(&self.check_command[1..])
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+    /// Directories holding the tool's settings, sessions and caches, whether or not they
+    /// exist: its config directories under the home directory (`~/.<binary>` unless it
+    /// declares any) and its XDG config, data and cache directories.
+    pub fn config_paths(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let home = paths::home_dir()?;
+        let mut dirs: Vec<PathBuf> = self.config_dirs.iter().map(|dir| home.join(dir)).collect();
+        match &self.xdg_dirs {
+            Some(name) => {
+                for base in [
+                    paths::config_home()?,
+                    paths::data_home()?,
+                    paths::cache_home()?,
+                ] {
+                    dirs.push(base.join(name));
+                }
+            }
+            None if dirs.is_empty() => {
+                let binary_name = self.binary_name.as_deref().unwrap_or(&self.name);
+                dirs.push(home.join(format!(".{}", binary_name)));
+            }
+            None => {}
+        }
+        Ok(dirs)
+    }
+
+    /// Where the tool may store a login.
+    pub fn auth_paths(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let home = paths::home_dir()?;
+        let mut files: Vec<PathBuf> = self.auth_files.iter().map(|file| home.join(file)).collect();
+        if !self.xdg_auth_files.is_empty() {
+            let data_home = paths::data_home()?;
+            files.extend(self.xdg_auth_files.iter().map(|file| data_home.join(file)));
+        }
+        Ok(files)
+    }
+
+    /// Where the bootstrap installer keeps every version it has downloaded.
+    pub fn versions_dir(&self) -> anyhow::Result<PathBuf> {
+        let binary_name = self.binary_name.as_deref().unwrap_or(&self.name);
+        Ok(paths::home_dir()?
+            .join(".local")
+            .join("share")
+            .join(binary_name)
+            .join("versions"))
     }
 }
 
@@ -90,6 +356,20 @@ pub struct ToolVersion {
     pub installed: Option<String>,
     pub latest: Option<String>,
     pub identifier: Option<String>,
+    pub method: Option<MethodKind>,
+    pub channel: Option<String>,
+    /// How old `latest` is when it came from the on-disk cache rather than a fresh lookup.
+    pub latest_age: Option<Duration>,
+    /// Seconds since the Unix epoch when `latest` was published, if the source reports it.
+    pub latest_published: Option<u64>,
+    /// Set when policies keep `upgrade --all` from installing `latest`.
+    pub held_back: Option<HeldBack>,
+    /// Why the latest-version lookup failed; `latest` may still hold a stale cached value.
+    pub latest_error: Option<LookupFailure>,
+    /// Why the primary source failed when `latest` came from its fallback instead.
+    pub latest_fallback: Option<LookupFailure>,
+    /// Set when the version command didn't finish within [`DETECTION_TIMEOUT`].
+    pub detection_timed_out: bool,
 }
 
 impl ToolVersion {
@@ -99,6 +379,14 @@ impl ToolVersion {
             installed: None,
             latest: None,
             identifier: None,
+            method: None,
+            channel: None,
+            latest_age: None,
+            latest_published: None,
+            held_back: None,
+            latest_error: None,
+            latest_fallback: None,
+            detection_timed_out: false,
         }
     }
 
@@ -111,6 +399,16 @@ impl ToolVersion {
         self.identifier = Some(identifier.to_string());
         self
     }
+
+    pub fn with_method(mut self, method: Option<MethodKind>) -> Self {
+        self.method = method;
+        self
+    }
+
+    pub fn with_channel(mut self, channel: Option<&str>) -> Self {
+        self.channel = channel.map(str::to_string);
+        self
+    }
 }
 
 pub fn catalog() -> Vec<Tool> {
@@ -128,33 +426,236 @@ pub fn catalog() -> Vec<Tool> {
     ]
 }
 
-pub fn installed_versions() -> Vec<ToolVersion> {
-    vec![
-        claude_installed_version(),
-        amp_installed_version(),
-        codex_installed_version(),
-        cursor_agent_installed_version(),
-        copilot_installed_version(),
-        kilo_installed_version(),
-        gemini_installed_version(),
-        cline_installed_version(),
-        opencode_installed_version(),
-        factory_cli_installed_version(),
-    ]
+/// How long a single tool's version command may take before it's reported as timed out.
+pub const DETECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hung version commands are killed shortly after detection gives up on them, so their
+/// blocking threads finish before the runtime shuts down.
+const COMMAND_KILL_AFTER: Duration = Duration::from_secs(11);
+
+/// Detects every tool concurrently, giving each at most [`DETECTION_TIMEOUT`].
+async fn installed_versions(config: &Config) -> Vec<ToolVersion> {
+    let catalog = catalog();
+    let detections = catalog.iter().map(|tool| {
+        let (check_command, parse_version) = (tool.check_command.clone(), tool.parse_version);
+        let task = tokio::task::spawn_blocking(move || {
+            run_check_command(&check_command).and_then(|output| parse_version(&output))
+        });
+        tokio::time::timeout(DETECTION_TIMEOUT, task)
+    });
+    let results = futures::future::join_all(detections).await;
+
+    catalog
+        .iter()
+        .zip(results)
+        .map(|(tool, result)| match result {
+            Ok(Ok(installed)) => {
+                let version = unknown_version(tool).with_installed(installed);
+                let method = version
+                    .installed
+                    .as_ref()
+                    .and_then(|_| tool.detect_install_method())
+                    .map(|m| m.kind());
+                version
+                    .with_method(method)
+                    .with_channel(config.channel(tool))
+            }
+            // A panicking detector is treated like a missing tool.
+            Ok(Err(_)) => unknown_version(tool).with_channel(config.channel(tool)),
+            Err(_) => {
+                let mut version = unknown_version(tool)
+                    .with_method(tool.detect_install_method().map(|m| m.kind()))
+                    .with_channel(config.channel(tool));
+                version.detection_timed_out = true;
+                version
+            }
+        })
+        .collect()
+}
+
+/// Detects `tool`'s installed version again, e.g. after installing or upgrading it.
+pub async fn detect_version(tool: &Tool) -> Option<String> {
+    version_output(tool).await?.1
+}
+
+/// Runs `tool`'s version command once, within [`DETECTION_TIMEOUT`], returning its output
+/// and the version parsed from it.
+pub async fn version_output(tool: &Tool) -> Option<(String, Option<String>)> {
+    let check_command = tool.check_command.clone();
+    let task = tokio::task::spawn_blocking(move || run_check_command(&check_command));
+    let output = tokio::time::timeout(DETECTION_TIMEOUT, task)
+        .await
+        .ok()?
+        .ok()??;
+    let version = (tool.parse_version)(&output);
+    Some((output, version))
+}
+
+/// Runs a tool's `check_command`, returning its trimmed output if it succeeds.
+fn run_check_command(check_command: &[String]) -> Option<String> {
+    let (program, args) = check_command.split_first()?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    command_output(program, &args)
+}
+
+/// Installed versions detected once per invocation, so each tool's version command runs at
+/// most once no matter how many times a command asks whether it's installed.
+#[derive(Debug)]
+pub struct Detection {
+    versions: Vec<ToolVersion>,
 }
 
+impl Detection {
+    pub async fn run(config: &Config) -> Self {
+        Self {
+            versions: installed_versions(config).await,
+        }
+    }
+
+    pub fn version(&self, tool: &Tool) -> Option<&ToolVersion> {
+        self.versions
+            .iter()
+            .find(|version| version.name == tool.name)
+    }
+
+    /// Whether `tool` is on this machine. A hung version command still means the binary exists.
+    pub fn is_installed(&self, tool: &Tool) -> bool {
+        self.version(tool)
+            .is_some_and(|version| version.installed.is_some() || version.detection_timed_out)
+    }
+
+    /// The install method detected for `tool`'s binary, if it's installed.
+    pub fn method(&self, tool: &Tool) -> Option<MethodKind> {
+        self.version(tool).and_then(|version| version.method)
+    }
+
+    pub fn into_versions(self) -> Vec<ToolVersion> {
+        self.versions
+    }
+}
+
+/// `tool`'s row before anything about its installation is known.
+fn unknown_version(tool: &Tool) -> ToolVersion {
+    let version = ToolVersion::new(&tool.name);
+    match &tool.binary_name {
+        Some(binary_name) => version.with_identifier(binary_name),
+        None => version,
+    }
+}
+
+fn classify_install_path(path: &Path) -> MethodKind {
+    let path = path.to_string_lossy();
+    // Homebrew formulae can bundle their own node_modules, so check the Cellar first.
+    if path.contains("/Cellar/") || path.contains("/Caskroom/") {
+        MethodKind::Brew
+    } else if path.contains("/node_modules/") {
+        MethodKind::Npm
+    } else {
+        MethodKind::Bootstrap
+    }
+}
+
+/// Runs `cmd` and returns its trimmed stdout if it succeeds, killing it if it runs
+/// longer than [`COMMAND_KILL_AFTER`].
 pub(crate) fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
-    Command::new(cmd)
+    let mut child = Command::new(cmd)
         .args(args)
-        .output()
-        .ok()
-        .and_then(|output| {
-            if output.status.success() {
-                String::from_utf8(output.stdout)
-                    .ok()
-                    .map(|s| s.trim().to_string())
-            } else {
-                None
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // Read on a separate thread so a chatty command can't block on a full pipe.
+    let mut stdout = child.stdout.take()?;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stdout.read_to_end(&mut buffer);
+        let _ = sender.send(buffer);
+    });
+
+    let deadline = Instant::now() + COMMAND_KILL_AFTER;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
             }
-        })
+        }
+    };
+    if !status.success() {
+        return None;
+    }
+
+    // Background processes forked by the command may keep stdout open after it exits.
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    let stdout = receiver.recv_timeout(remaining).ok()?;
+    String::from_utf8(stdout).ok().map(|s| s.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MethodKind, catalog, classify_install_path};
+    use std::path::Path;
+
+    #[test]
+    fn it_classifies_install_paths_by_package_manager() {
+        let cases = [
+            (
+                "/opt/homebrew/Cellar/gemini-cli/0.9.0/libexec/lib/node_modules/@google/gemini-cli/dist/index.js",
+                MethodKind::Brew,
+            ),
+            (
+                "/usr/local/lib/node_modules/@anthropic-ai/claude-code/cli.js",
+                MethodKind::Npm,
+            ),
+            (
+                "/home/user/.local/share/claude/versions/1.0.44",
+                MethodKind::Bootstrap,
+            ),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(classify_install_path(Path::new(path)), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn it_parses_version_output_with_each_tools_parser() {
+        let parse = |name: &str, output: &str| {
+            let tool = catalog()
+                .into_iter()
+                .find(|tool| tool.name == name)
+                .unwrap();
+            (tool.parse_version)(output)
+        };
+        assert_eq!(
+            parse("Claude Code", "2.0.14 (Claude Code)").as_deref(),
+            Some("2.0.14")
+        );
+        assert_eq!(
+            parse("Amp", "0.0.1760 (released 2025-10-10)").as_deref(),
+            Some("0.0.1760")
+        );
+        assert_eq!(
+            parse("Codex CLI", "codex-cli 0.46.0").as_deref(),
+            Some("0.46.0")
+        );
+        assert_eq!(
+            parse(
+                "Cline CLI",
+                "Cline CLI Version: 1.0.3\nCline Core Version: 3.32.0"
+            )
+            .as_deref(),
+            Some("1.0.3 (Core: 3.32.0)")
+        );
+        assert_eq!(
+            parse("Factory CLI", "Checking for updates...\nv0.19.6").as_deref(),
+            Some("0.19.6")
+        );
+    }
 }
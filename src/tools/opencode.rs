@@ -1,19 +1,25 @@
-use super::{InstallMethod, Tool, ToolVersion, command_output};
+use super::{InstallMethod, Tool};
 
 pub fn definition() -> Tool {
     Tool::new(
         "OpenCode",
         InstallMethod::Bootstrap("https://opencode.ai/install".to_string()),
         vec!["opencode".to_string(), "--version".to_string()],
+        parse_version,
     )
+    .with_install_method(InstallMethod::Npm("opencode-ai".to_string()))
+    .with_install_method(InstallMethod::Brew("opencode".to_string()))
     .with_binary_name("opencode")
     .with_config_dir(".opencode")
     .with_extra_binary_path(".opencode/bin/opencode")
+    .with_description("Open-source AI coding agent built for the terminal")
+    .with_vendor("SST")
+    .with_homepage("https://opencode.ai")
+    .with_docs_url("https://opencode.ai/docs")
+    .with_license("MIT; bring your own model provider")
+    .with_xdg_auth_file("opencode/auth.json")
 }
 
-pub fn installed_version() -> ToolVersion {
-    let installed = command_output("opencode", &["--version"]);
-    ToolVersion::new("OpenCode")
-        .with_installed(installed)
-        .with_identifier("opencode")
+fn parse_version(output: &str) -> Option<String> {
+    Some(output.to_string())
 }
@@ -1,27 +1,39 @@
-use std::{collections::HashMap, process::Command};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, io,
+    sync::LazyLock,
+    time::Duration,
+};
 
+use clap::ValueEnum;
 use colored::*;
 use futures::future::join_all;
-use indicatif::{ProgressBar, ProgressStyle};
-use serde::Deserialize;
+use regex::Regex;
+use reqwest::{
+    StatusCode,
+    header::{self, HeaderMap},
+};
+use serde::{Deserialize, Serialize};
 use tokio::task;
 
-use crate::tools::ToolVersion;
+use crate::{
+    cache::{self, CacheEntry, LatestCache, Validators, cache_key, format_age},
+    config::Config,
+    github::{self, RateLimit},
+    http,
+    output::{self, Progress},
+    registry::Registries,
+    tools::{CLAUDE_RELEASES_URL, DEFAULT_CHANNEL, ToolVersion},
+};
 
 #[derive(Deserialize)]
 struct NpmPackageInfo {
+    /// Release channels (`latest`, `next`, `beta`, ...) mapped to versions.
     #[serde(rename = "dist-tags")]
-    dist_tags: NpmDistTags,
-}
-
-#[derive(Deserialize)]
-struct NpmDistTags {
-    latest: String,
-}
-
-#[derive(Deserialize)]
-struct GitHubRelease {
-    tag_name: String,
+    dist_tags: HashMap<String, String>,
+    /// Publish timestamps keyed by version.
+    #[serde(default)]
+    time: HashMap<String, String>,
 }
 
 #[derive(Deserialize)]
@@ -45,63 +57,521 @@ struct BrewCask {
     version: String,
 }
 
-async fn get_factory_cli_latest() -> Option<String> {
-    let script = reqwest::get("https://app.factory.ai/cli")
-        .await
-        .ok()?
-        .text()
-        .await
-        .ok()?;
+/// Where a tool's latest published version is looked up.
+#[derive(Debug, Clone, Copy)]
+enum LatestSource {
+    /// Claude Code's bootstrap release manifest, with npm as a fallback.
+    ClaudeManifest,
+    Npm(&'static str),
+    /// Homebrew only publishes one version, so the channel is ignored.
+    Brew(&'static str),
+    /// A vendor installer script that pins the version it downloads; the first capture group of
+    /// `pattern` is the version.
+    InstallerScript {
+        url: &'static str,
+        pattern: &'static str,
+    },
+}
+
+/// Everything a source needs for one lookup, owned so it can move into a spawned task.
+struct LookupRequest {
+    channel: String,
+    validators: Option<Validators>,
+    /// Validators cached for the fallback source, for sources that have one.
+    fallback_validators: Option<Validators>,
+    registries: Registries,
+    /// Mirror of the tool's installer script, replacing the vendor URL.
+    installer_url: Option<String>,
+}
+
+impl LatestSource {
+    async fn lookup(self, request: LookupRequest) -> Result<Lookup, LookupError> {
+        let LookupRequest {
+            channel,
+            validators,
+            fallback_validators,
+            registries,
+            installer_url,
+        } = request;
+        let validators = validators.as_ref();
+        match self {
+            LatestSource::ClaudeManifest => {
+                lookup_claude(
+                    &format!("{}/{}", CLAUDE_RELEASES_URL, channel),
+                    &registries.npm_package_url("@anthropic-ai/claude-code"),
+                    &channel,
+                    validators,
+                    fallback_validators.as_ref(),
+                )
+                .await
+            }
+            LatestSource::Npm(package) => {
+                lookup_npm(&registries.npm_package_url(package), &channel, validators).await
+            }
+            LatestSource::Brew(formula) => get_brew_latest(formula, registries)
+                .await
+                .map(|version| Lookup::found(version.into())),
+            LatestSource::InstallerScript { url, pattern } => {
+                let url = installer_url.as_deref().unwrap_or(url);
+                lookup_script(url, pattern, validators).await
+            }
+        }
+    }
+
+    fn uses_brew(&self) -> bool {
+        matches!(self, LatestSource::Brew(_))
+    }
+}
 
-    script
-        .lines()
-        .find_map(|line| line.trim().strip_prefix("VER=").map(|value| value.trim()))
-        .map(|value| value.trim_matches(|c| c == '"' || c == '\'').to_string())
+/// Outcome of a latest-version lookup.
+#[derive(Debug)]
+enum Lookup {
+    /// Upstream answered 304 to the cached validators, so the cached version still stands.
+    NotModified,
+    Found {
+        version: String,
+        /// Seconds since the Unix epoch, when the source reports it.
+        published_at: Option<u64>,
+        validators: Validators,
+    },
+    /// The primary source failed and the fallback source answered instead.
+    Fallback {
+        primary: LookupError,
+        lookup: Box<Lookup>,
+    },
 }
 
-async fn fetch_npm_latest(url: &str) -> Option<String> {
-    let response = reqwest::get(url).await.ok()?;
-    let info: NpmPackageInfo = response.json().await.ok()?;
-    Some(info.dist_tags.latest)
+impl Lookup {
+    fn found(latest: Latest) -> Self {
+        Lookup::Found {
+            version: latest.version,
+            published_at: latest.published_at,
+            validators: Validators::default(),
+        }
+    }
+}
+
+/// A version parsed from an upstream response.
+struct Latest {
+    version: String,
+    published_at: Option<u64>,
+}
+
+impl From<String> for Latest {
+    fn from(version: String) -> Self {
+        Self {
+            version,
+            published_at: None,
+        }
+    }
+}
+
+/// Seconds since the Unix epoch for an RFC 3339 timestamp, as npm and GitHub report them.
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .and_then(|time| u64::try_from(time.timestamp()).ok())
 }
 
-async fn get_npm_latest(package: &str) -> Option<String> {
-    let url = format!("https://registry.npmjs.org/{}", package);
-    fetch_npm_latest(&url).await
+/// Why a latest-version lookup failed.
+#[derive(Debug)]
+pub enum LookupError {
+    /// No response at all: DNS, connection, TLS or timeout.
+    Network {
+        url: String,
+        source: reqwest::Error,
+    },
+    Status {
+        url: String,
+        status: StatusCode,
+    },
+    RateLimited {
+        url: String,
+        rate_limit: RateLimit,
+    },
+    /// A response arrived but no longer has the shape we parse, e.g. an upstream format change.
+    InvalidResponse {
+        url: String,
+        detail: String,
+    },
+    MissingChannel {
+        url: String,
+        channel: String,
+    },
+    Command {
+        command: String,
+        detail: String,
+        source: Option<io::Error>,
+    },
+    Task(task::JoinError),
+    /// The primary source failed, and so did the fallback tried after it.
+    Fallback {
+        primary: Box<LookupError>,
+        fallback: Box<LookupError>,
+    },
 }
 
-async fn get_github_latest(repo: &str) -> Option<String> {
-    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .header("User-Agent", "ai-cli-apps")
-        .send()
+impl LookupError {
+    pub(crate) fn invalid(url: &str, detail: impl fmt::Display) -> Self {
+        LookupError::InvalidResponse {
+            url: url.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+
+    /// A few words for the version list.
+    pub fn reason(&self) -> String {
+        match self {
+            LookupError::Network { source, .. } if source.is_timeout() => "timed out".to_string(),
+            LookupError::Network { .. } => "network error".to_string(),
+            LookupError::Status { status, .. } => format!("HTTP {}", status.as_u16()),
+            LookupError::RateLimited { rate_limit, .. } => rate_limit.describe(),
+            LookupError::InvalidResponse { .. } => "unexpected response format".to_string(),
+            LookupError::MissingChannel { channel, .. } => format!("no '{}' release", channel),
+            LookupError::Command { command, .. } => format!("`{}` failed", command),
+            LookupError::Task(_) => "lookup crashed".to_string(),
+            LookupError::Fallback { fallback, .. } => fallback.reason(),
+        }
+    }
+
+    /// This error followed by each underlying cause, for `--verbose`.
+    pub fn chain(&self) -> Vec<String> {
+        if let LookupError::Fallback { primary, fallback } = self {
+            let mut chain = fallback.chain();
+            let mut primary = primary.chain().into_iter();
+            if let Some(first) = primary.next() {
+                chain.push(format!("fell back after: {}", first));
+            }
+            chain.extend(primary);
+            return chain;
+        }
+        let mut chain = vec![self.to_string()];
+        let mut source = std::error::Error::source(self);
+        while let Some(cause) = source {
+            chain.push(cause.to_string());
+            source = cause.source();
+        }
+        chain
+    }
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupError::Network { url, .. } => write!(f, "request to {} failed", url),
+            LookupError::Status { url, status } => write!(f, "{} returned {}", url, status),
+            LookupError::RateLimited { url, rate_limit } => {
+                write!(f, "{} is {}", url, rate_limit.describe())
+            }
+            LookupError::InvalidResponse { url, detail } => {
+                write!(f, "unexpected response from {}: {}", url, detail)
+            }
+            LookupError::MissingChannel { url, channel } => {
+                write!(f, "{} has no '{}' dist-tag", url, channel)
+            }
+            LookupError::Command {
+                command, detail, ..
+            } => write!(f, "`{}` failed: {}", command, detail),
+            LookupError::Task(_) => write!(f, "lookup task did not complete"),
+            LookupError::Fallback { fallback, .. } => fallback.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LookupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LookupError::Network { source, .. } => Some(source),
+            LookupError::Command {
+                source: Some(source),
+                ..
+            } => Some(source),
+            LookupError::Task(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A failed lookup as shown in the version list.
+#[derive(Debug, Clone)]
+pub struct LookupFailure {
+    pub reason: String,
+    pub chain: Vec<String>,
+}
+
+impl From<&LookupError> for LookupFailure {
+    fn from(error: &LookupError) -> Self {
+        Self {
+            reason: error.reason(),
+            chain: error.chain(),
+        }
+    }
+}
+
+enum HttpResponse {
+    NotModified,
+    Body {
+        body: String,
+        validators: Validators,
+        /// The next page of a paginated response.
+        next_page: Option<String>,
+    },
+}
+
+async fn conditional_get(
+    url: &str,
+    validators: Option<&Validators>,
+    mut headers: HeaderMap,
+) -> Result<HttpResponse, LookupError> {
+    if let Some(validators) = validators {
+        if let Some(etag) = validators.etag.as_deref().and_then(|v| v.parse().ok()) {
+            headers.insert(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = validators
+            .last_modified
+            .as_deref()
+            .and_then(|v| v.parse().ok())
+        {
+            headers.insert(header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let network_error = |source| LookupError::Network {
+        url: url.to_string(),
+        source,
+    };
+    let response = http::client()
+        .get(url, headers)
         .await
-        .ok()?;
-    let release: GitHubRelease = response.json().await.ok()?;
-    Some(release.tag_name)
+        .map_err(network_error)?;
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(HttpResponse::NotModified);
+    }
+    let rate_limit = RateLimit::from_headers(response.headers());
+    if matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) && rate_limit.is_exhausted()
+    {
+        return Err(LookupError::RateLimited {
+            url: url.to_string(),
+            rate_limit,
+        });
+    }
+    if !status.is_success() {
+        return Err(LookupError::Status {
+            url: url.to_string(),
+            status,
+        });
+    }
+
+    let header_value = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value: &header::HeaderValue| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let validators = Validators {
+        etag: header_value(header::ETAG),
+        last_modified: header_value(header::LAST_MODIFIED),
+    };
+    let next_page = github::next_page(response.headers());
+    let body = response.text().await.map_err(network_error)?;
+    Ok(HttpResponse::Body {
+        body,
+        validators,
+        next_page,
+    })
+}
+
+/// Fetches `url` without validators, with the same error handling as version lookups.
+pub(crate) async fn fetch_text(url: &str, headers: HeaderMap) -> Result<String, LookupError> {
+    fetch_page(url, headers).await.map(|(body, _)| body)
 }
 
-fn update_brew() -> bool {
-    Command::new("brew")
+/// Like [`fetch_text`], along with the URL of the next page from the `Link` header.
+pub(crate) async fn fetch_page(
+    url: &str,
+    headers: HeaderMap,
+) -> Result<(String, Option<String>), LookupError> {
+    match conditional_get(url, None, headers).await? {
+        HttpResponse::Body {
+            body, next_page, ..
+        } => Ok((body, next_page)),
+        HttpResponse::NotModified => Err(LookupError::invalid(url, "unexpected 304 Not Modified")),
+    }
+}
+
+/// Fetches `url` and extracts a version from the body with `parse`.
+async fn lookup_with(
+    url: &str,
+    validators: Option<&Validators>,
+    headers: HeaderMap,
+    parse: impl FnOnce(&str) -> Result<Latest, LookupError>,
+) -> Result<Lookup, LookupError> {
+    match conditional_get(url, validators, headers).await? {
+        HttpResponse::NotModified => Ok(Lookup::NotModified),
+        HttpResponse::Body {
+            body, validators, ..
+        } => parse(&body).map(|latest| Lookup::Found {
+            version: latest.version,
+            published_at: latest.published_at,
+            validators,
+        }),
+    }
+}
+
+const LATEST_SOURCES: &[(&str, LatestSource)] = &[
+    ("Claude Code", LatestSource::ClaudeManifest),
+    ("Amp", LatestSource::Npm("@sourcegraph/amp")),
+    ("Codex CLI", LatestSource::Brew("codex")),
+    (
+        "Cursor CLI",
+        LatestSource::InstallerScript {
+            url: "https://cursor.com/install",
+            pattern: r"downloads\.cursor\.com/lab/([^/\s]+)/",
+        },
+    ),
+    ("Copilot CLI", LatestSource::Npm("@github/copilot")),
+    ("Gemini CLI", LatestSource::Brew("gemini-cli")),
+    ("Cline CLI", LatestSource::Npm("cline")),
+    ("Kilo Code CLI", LatestSource::Npm("@kilocode/cli")),
+    ("OpenCode", LatestSource::Brew("opencode")),
+    (
+        "Factory CLI",
+        LatestSource::InstallerScript {
+            url: "https://app.factory.ai/cli",
+            pattern: r#"(?m)^\s*VER=["']?([^"'\s]+)"#,
+        },
+    ),
+];
+
+async fn lookup_script(
+    url: &str,
+    pattern: &str,
+    validators: Option<&Validators>,
+) -> Result<Lookup, LookupError> {
+    let regex = Regex::new(pattern).map_err(|e| LookupError::invalid(url, e))?;
+    lookup_with(url, validators, HeaderMap::new(), |script| {
+        regex
+            .captures(script)
+            .and_then(|captures| captures.get(1))
+            .map(|version| version.as_str().to_string().into())
+            .ok_or_else(|| {
+                LookupError::invalid(url, format!("no version matching `{}` in script", pattern))
+            })
+    })
+    .await
+}
+
+async fn lookup_npm(
+    url: &str,
+    channel: &str,
+    validators: Option<&Validators>,
+) -> Result<Lookup, LookupError> {
+    lookup_with(url, validators, HeaderMap::new(), |body| {
+        let mut info: NpmPackageInfo =
+            serde_json::from_str(body).map_err(|e| LookupError::invalid(url, e))?;
+        let version =
+            info.dist_tags
+                .remove(channel)
+                .ok_or_else(|| LookupError::MissingChannel {
+                    url: url.to_string(),
+                    channel: channel.to_string(),
+                })?;
+        let published_at = info
+            .time
+            .get(&version)
+            .and_then(|time| parse_timestamp(time));
+        Ok(Latest {
+            version,
+            published_at,
+        })
+    })
+    .await
+}
+
+/// Every release channel `url`'s npm package publishes, mapped to its version.
+pub async fn npm_dist_tags(url: &str) -> Result<BTreeMap<String, String>, LookupError> {
+    let body = fetch_text(url, HeaderMap::new()).await?;
+    let info: NpmPackageInfo =
+        serde_json::from_str(&body).map_err(|e| LookupError::invalid(url, e))?;
+    Ok(info.dist_tags.into_iter().collect())
+}
+
+fn parse_release_manifest(url: &str, body: &str) -> Result<String, LookupError> {
+    let version = body.trim();
+    // The manifest is a bare version string; anything else is an error page.
+    if version.starts_with(|c: char| c.is_ascii_digit()) && !version.contains(char::is_whitespace) {
+        Ok(version.to_string())
+    } else {
+        Err(LookupError::invalid(url, "manifest is not a bare version"))
+    }
+}
+
+async fn lookup_claude(
+    manifest_url: &str,
+    npm_url: &str,
+    channel: &str,
+    validators: Option<&Validators>,
+    npm_validators: Option<&Validators>,
+) -> Result<Lookup, LookupError> {
+    match lookup_with(manifest_url, validators, HeaderMap::new(), |body| {
+        parse_release_manifest(manifest_url, body).map(Latest::from)
+    })
+    .await
+    {
+        Ok(lookup) => Ok(lookup),
+        Err(primary) => match lookup_npm(npm_url, channel, npm_validators).await {
+            Ok(lookup) => Ok(Lookup::Fallback {
+                primary,
+                lookup: Box::new(lookup),
+            }),
+            Err(fallback) => Err(LookupError::Fallback {
+                primary: Box::new(primary),
+                fallback: Box::new(fallback),
+            }),
+        },
+    }
+}
+
+fn update_brew(registries: &Registries) -> bool {
+    registries
+        .brew_command()
         .args(["update"])
         .output()
         .map(|output| output.status.success())
         .unwrap_or(false)
 }
 
-async fn get_brew_latest(formula: &str) -> Option<String> {
+async fn get_brew_latest(formula: &str, registries: Registries) -> Result<String, LookupError> {
     let formula = formula.to_string();
     task::spawn_blocking(move || {
-        let output = Command::new("brew")
+        let command = format!("brew info --json=v2 {}", formula);
+        let output = registries
+            .brew_command()
             .args(["info", "--json=v2", &formula])
             .output()
-            .ok()?;
+            .map_err(|e| LookupError::Command {
+                command: command.clone(),
+                detail: "could not run brew".to_string(),
+                source: Some(e),
+            })?;
         if !output.status.success() {
-            return None;
+            return Err(LookupError::Command {
+                command,
+                detail: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                source: None,
+            });
         }
-        let info: BrewInfo = serde_json::from_slice(&output.stdout).ok()?;
+        let info: BrewInfo =
+            serde_json::from_slice(&output.stdout).map_err(|e| LookupError::Command {
+                command: command.clone(),
+                detail: format!("unexpected JSON: {}", e),
+                source: None,
+            })?;
 
         // Check formulae first
         if let Some(formula_version) = info
@@ -110,18 +580,25 @@ async fn get_brew_latest(formula: &str) -> Option<String> {
             .next()
             .and_then(|f| f.versions.stable)
         {
-            return Some(formula_version);
+            return Ok(formula_version);
         }
 
         // Fall back to casks
-        info.casks.into_iter().next().map(|c| c.version)
+        info.casks
+            .into_iter()
+            .next()
+            .map(|c| c.version)
+            .ok_or_else(|| LookupError::Command {
+                command,
+                detail: "no stable formula or cask version".to_string(),
+                source: None,
+            })
     })
     .await
-    .ok()
-    .flatten()
+    .map_err(LookupError::Task)?
 }
 
-fn is_newer_version(latest: &str, installed: &str) -> bool {
+pub(crate) fn is_newer_version(latest: &str, installed: &str) -> bool {
     // Extract numeric parts from version strings
     let parse_version = |v: &str| -> Vec<u32> {
         v.trim_start_matches('v')
@@ -148,64 +625,396 @@ fn is_newer_version(latest: &str, installed: &str) -> bool {
     false
 }
 
-pub async fn check_latest_versions(tools: &mut [ToolVersion]) {
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.cyan} {msg}")
-            .unwrap(),
-    );
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+/// `check` exit code when an installed tool has a newer version available.
+pub const EXIT_OUTDATED: i32 = 10;
+/// `check` exit code when a tool named on the command line isn't installed.
+pub const EXIT_MISSING: i32 = 20;
+/// `check` exit code when a lookup or detection failed, so freshness couldn't be verified.
+pub const EXIT_LOOKUP_FAILED: i32 = 30;
+
+/// Conditions that make `check` exit non-zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// An installed tool is behind its latest version
+    Outdated,
+    /// A required tool isn't installed
+    Missing,
+    /// A lookup or detection failed, so freshness couldn't be verified
+    LookupFailed,
+}
+
+pub fn is_outdated(tool: &ToolVersion) -> bool {
+    match (&tool.installed, &tool.latest) {
+        (Some(installed), Some(latest)) => {
+            !(installed.contains(latest) || latest.contains(installed))
+                && is_newer_version(latest, installed)
+        }
+        _ => false,
+    }
+}
+
+/// How far an unattended upgrade may move a tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpgradePolicy {
+    /// Only patch releases (1.2.3 → 1.2.4)
+    Patch,
+    /// Minor and patch releases (1.2.3 → 1.3.0)
+    Minor,
+    /// Any newer release
+    #[default]
+    #[value(alias = "major")]
+    #[serde(alias = "major")]
+    All,
+}
+
+impl UpgradePolicy {
+    /// Whether going from `installed` to `latest` stays within the policy. Versions without
+    /// a major.minor number are only allowed by `All`.
+    pub fn allows(self, installed: &str, latest: &str) -> bool {
+        let same_prefix = |len: usize| {
+            let installed = version_numbers(installed);
+            let latest = version_numbers(latest);
+            installed.len() >= 2 && latest.len() >= 2 && installed[..len] == latest[..len]
+        };
+        match self {
+            UpgradePolicy::Patch => same_prefix(2),
+            UpgradePolicy::Minor => same_prefix(1),
+            UpgradePolicy::All => true,
+        }
+    }
+}
+
+/// Why an available update isn't installed by `upgrade --all` or scheduled upgrades.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeldBack {
+    /// The release is a bigger step than the policy allows.
+    Policy(UpgradePolicy),
+    /// The release is younger than the configured minimum age.
+    TooNew { age: Duration, min_age: Duration },
+    /// The tool is pinned to a version in the config.
+    Pinned(String),
+}
+
+impl fmt::Display for HeldBack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeldBack::Policy(UpgradePolicy::Patch) => {
+                write!(f, "policy allows patch releases only")
+            }
+            HeldBack::Policy(UpgradePolicy::Minor) => {
+                write!(f, "policy allows minor and patch releases only")
+            }
+            HeldBack::Policy(UpgradePolicy::All) => write!(f, "policy"),
+            HeldBack::Pinned(version) => write!(f, "pinned to {}", version),
+            HeldBack::TooNew { age, min_age } => write!(
+                f,
+                "released {} ago, waiting until it is {} old",
+                format_age(*age),
+                format_age(*min_age)
+            ),
+        }
+    }
+}
+
+/// Whether `tool`'s available update is held back by `policy` or a minimum release age.
+/// Releases without a known publish time aren't held back by age.
+pub fn held_back(tool: &ToolVersion, policy: UpgradePolicy, min_age: Duration) -> Option<HeldBack> {
+    let (Some(installed), Some(latest)) = (&tool.installed, &tool.latest) else {
+        return None;
+    };
+    if !is_outdated(tool) {
+        return None;
+    }
+    if !policy.allows(installed, latest) {
+        return Some(HeldBack::Policy(policy));
+    }
+    let age = Duration::from_secs(cache::now().saturating_sub(tool.latest_published?));
+    (age < min_age).then_some(HeldBack::TooNew { age, min_age })
+}
+
+static VERSION_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+(?:\.\d+)*").unwrap());
+
+/// The first dotted number in `version`, e.g. `[1, 2, 3]` for "1.2.3 (Claude Code)".
+fn version_numbers(version: &str) -> Vec<u32> {
+    VERSION_NUMBER
+        .find(version)
+        .map(|m| {
+            m.as_str()
+                .split('.')
+                .filter_map(|n| n.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Exit code for `check`: the highest code among the conditions in `fail_on`, or 0.
+/// Every tool in `tools` counts as required when `required` is set.
+pub fn check_exit_code(tools: &[ToolVersion], required: bool, fail_on: &[FailOn]) -> i32 {
+    let outdated = tools.iter().any(is_outdated);
+    let unverified = tools.iter().any(|tool| {
+        tool.detection_timed_out || (tool.installed.is_some() && tool.latest_error.is_some())
+    });
+    let missing = required
+        && tools
+            .iter()
+            .any(|tool| tool.installed.is_none() && !tool.detection_timed_out);
+
+    [
+        (FailOn::Outdated, outdated, EXIT_OUTDATED),
+        (FailOn::Missing, missing, EXIT_MISSING),
+        (FailOn::LookupFailed, unverified, EXIT_LOOKUP_FAILED),
+    ]
+    .into_iter()
+    .filter(|(condition, hit, _)| *hit && (fail_on.is_empty() || fail_on.contains(condition)))
+    .map(|(_, _, code)| code)
+    .max()
+    .unwrap_or(0)
+}
+
+/// How `check_latest_versions` uses the on-disk cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Use cached versions younger than the TTL, refresh the rest.
+    Default,
+    /// Ignore cached versions and query every source.
+    Refresh,
+    /// Never touch the network; use whatever is cached, however old.
+    Offline,
+}
+
+pub async fn check_latest_versions(tools: &mut [ToolVersion], config: &Config, mode: CacheMode) {
+    let mut cache = LatestCache::load();
+    let ttl = config.cache.ttl();
+
+    let registries = Registries::resolve(&config.registries);
+    let mut pending = Vec::new();
+    for tool in tools.iter_mut() {
+        let Some((_, source)) = LATEST_SOURCES.iter().find(|(name, _)| *name == tool.name) else {
+            continue;
+        };
+        let channel = tool
+            .channel
+            .as_deref()
+            .unwrap_or(DEFAULT_CHANNEL)
+            .to_string();
+        let key = cache_key(&tool.name, &channel);
+        let primary = cache.get(&key);
+        let fallback = cache.get(&cache::fallback_key(&key));
+        // Whichever source answered last holds the current latest version.
+        let cached = match (primary, fallback) {
+            (Some(primary), Some(fallback)) if fallback.age() < primary.age() => Some(fallback),
+            (primary, fallback) => primary.or(fallback),
+        };
+
+        match (mode, cached) {
+            (CacheMode::Offline, Some(entry)) => use_cached(tool, entry),
+            (CacheMode::Default, Some(entry)) if entry.is_fresh(ttl) => use_cached(tool, entry),
+            // Nothing to go on, so freshness can't be verified.
+            (CacheMode::Offline, None) => {
+                tool.latest_error = Some(LookupFailure {
+                    reason: "not cached".to_string(),
+                    chain: vec!["no cached latest version; run without --offline once".to_string()],
+                });
+            }
+            _ => {
+                let (validators, fallback_validators) = match mode {
+                    CacheMode::Refresh => (None, None),
+                    _ => (
+                        primary.map(|entry| entry.validators.clone()),
+                        fallback.map(|entry| entry.validators.clone()),
+                    ),
+                };
+                let installer_url = config
+                    .tool_named(&tool.name)
+                    .and_then(|tool_config| tool_config.installer_url.clone());
+                let request = LookupRequest {
+                    channel,
+                    validators,
+                    fallback_validators,
+                    registries: registries.clone(),
+                    installer_url,
+                };
+                pending.push((tool.name.clone(), key, *source, request));
+            }
+        }
+    }
 
     // Update Homebrew package database before checking versions
-    spinner.set_message("Updating Homebrew...");
-    task::spawn_blocking(|| {
-        update_brew();
-    })
-    .await
-    .ok();
+    let uses_brew = pending.iter().any(|(_, _, source, _)| source.uses_brew());
+    let progress = Progress::start(if uses_brew {
+        "Updating Homebrew..."
+    } else {
+        "Fetching versions..."
+    });
+    if uses_brew {
+        let registries = registries.clone();
+        task::spawn_blocking(move || {
+            update_brew(&registries);
+        })
+        .await
+        .ok();
+        progress.set_message("Fetching versions...");
+    }
 
-    spinner.set_message("Fetching versions...");
-    let sources = vec![
-        (
-            "Claude Code",
-            tokio::spawn(get_github_latest("anthropics/anthropic-quickstarts")),
-        ),
-        ("Amp", tokio::spawn(get_npm_latest("@sourcegraph/amp"))),
-        ("Codex CLI", tokio::spawn(get_brew_latest("codex"))),
-        (
-            "Copilot CLI",
-            tokio::spawn(get_npm_latest("@github/copilot")),
-        ),
-        ("Gemini CLI", tokio::spawn(get_brew_latest("gemini-cli"))),
-        ("Cline CLI", tokio::spawn(get_npm_latest("cline"))),
+    let sources: Vec<_> = pending
+        .into_iter()
+        .map(|(name, key, source, request)| (name, key, tokio::spawn(source.lookup(request))))
+        .collect();
+
+    let resolved = join_all(sources.into_iter().map(|(name, key, handle)| async move {
         (
-            "Kilo Code CLI",
-            tokio::spawn(get_npm_latest("@kilocode/cli")),
-        ),
-        ("OpenCode", tokio::spawn(get_brew_latest("opencode"))),
-        ("Factory CLI", tokio::spawn(get_factory_cli_latest())),
-    ];
-
-    let resolved = join_all(
-        sources
-            .into_iter()
-            .map(|(name, handle)| async move { (name, handle.await.ok().and_then(|r| r)) }),
-    )
+            name,
+            key,
+            handle.await.map_err(LookupError::Task).and_then(|r| r),
+        )
+    }))
     .await;
 
-    let latest_map: HashMap<_, _> = resolved.into_iter().collect();
+    for (name, key, lookup) in resolved {
+        let Some(tool) = tools.iter_mut().find(|t| t.name == name) else {
+            continue;
+        };
+
+        let (key, lookup) = match lookup {
+            Ok(Lookup::Fallback { primary, lookup }) => {
+                tool.latest_fallback = Some(LookupFailure::from(&primary));
+                (cache::fallback_key(&key), Ok(*lookup))
+            }
+            lookup => (key, lookup),
+        };
+        match lookup {
+            Ok(Lookup::Found {
+                version,
+                published_at,
+                validators,
+            }) => {
+                tool.latest = Some(version.clone());
+                tool.latest_published = published_at;
+                cache.insert(key, CacheEntry::new(version, published_at, validators));
+            }
+            Ok(Lookup::NotModified) => {
+                if let Some(mut entry) = cache.get(&key).cloned() {
+                    entry.touch();
+                    tool.latest = Some(entry.version.clone());
+                    tool.latest_published = entry.published_at;
+                    cache.insert(key, entry);
+                }
+            }
+            Ok(Lookup::Fallback { .. }) => {}
+            Err(error) => {
+                tool.latest_error = Some(LookupFailure::from(&error));
+                // Fall back to stale cached data rather than showing nothing.
+                if let Some(entry) = cache
+                    .get(&key)
+                    .or_else(|| cache.get(&cache::fallback_key(&key)))
+                {
+                    use_cached(tool, entry);
+                }
+            }
+        }
+    }
+
+    if mode != CacheMode::Offline
+        && let Err(error) = cache.save()
+        && output::verbose()
+    {
+        eprintln!(
+            "{} {:#}",
+            "Could not save the version cache:".yellow(),
+            error
+        );
+    }
 
     for tool in tools.iter_mut() {
-        if let Some(latest) = latest_map.get(tool.name.as_str()) {
-            tool.latest = latest.clone();
+        let pin = config.tool_named(&tool.name).and_then(|c| c.pin.clone());
+        tool.held_back = match pin {
+            Some(pin) if is_outdated(tool) => Some(HeldBack::Pinned(pin)),
+            _ => held_back(
+                tool,
+                config.upgrade_policy(&tool.name),
+                config.min_release_age(&tool.name),
+            ),
+        };
+    }
+
+    progress.finish();
+}
+
+fn use_cached(tool: &mut ToolVersion, entry: &CacheEntry) {
+    tool.latest = Some(entry.version.clone());
+    tool.latest_published = entry.published_at;
+    tool.latest_age = Some(entry.age());
+}
+
+/// Prints the full error chain for every failed lookup, for `--verbose`.
+pub fn print_lookup_errors(tools: &[ToolVersion]) {
+    let failed: Vec<_> = tools
+        .iter()
+        .flat_map(|tool| {
+            let fallback = tool
+                .latest_fallback
+                .as_ref()
+                .map(|f| (tool, f, " (used the fallback)"));
+            let error = tool.latest_error.as_ref().map(|f| (tool, f, ""));
+            fallback.into_iter().chain(error)
+        })
+        .collect();
+    if failed.is_empty() {
+        return;
+    }
+
+    println!("\n{}", "Lookup errors:".yellow().bold());
+    for (tool, failure, note) in failed {
+        let mut chain = failure.chain.iter();
+        if let Some(first) = chain.next() {
+            println!(
+                "  {} {}{}",
+                format!("{}:", tool.name).bold(),
+                first,
+                note.bright_black()
+            );
+        }
+        for cause in chain {
+            println!("    {} {}", "caused by:".bright_black(), cause);
         }
     }
+}
+
+/// Notes how old the cached latest versions are, if any were shown.
+pub fn print_cache_notice(tools: &[ToolVersion], mode: CacheMode) {
+    let Some(oldest) = tools.iter().filter_map(|t| t.latest_age).max() else {
+        if mode == CacheMode::Offline {
+            println!(
+                "\n{}",
+                "No cached latest versions yet; run without --offline once.".bright_black()
+            );
+        }
+        return;
+    };
 
-    spinner.finish_and_clear();
+    let hint = match mode {
+        CacheMode::Offline => "offline",
+        _ => "use --refresh to update",
+    };
+    println!(
+        "\n{}",
+        format!(
+            "Latest versions from cache, up to {} old ({})",
+            format_age(oldest),
+            hint
+        )
+        .bright_black()
+    );
 }
 
 pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize, id_width: usize) {
+    let channel = tool
+        .channel
+        .as_deref()
+        .filter(|channel| *channel != DEFAULT_CHANNEL)
+        .map(|channel| format!(" [{}]", channel).bright_black().to_string())
+        .unwrap_or_default();
     let status = match &tool.installed {
         Some(version) => {
             let version_str = version.to_string();
@@ -214,10 +1023,17 @@ pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize,
                     if version.contains(latest) || latest.contains(version) {
                         version_str.green().to_string()
                     } else if is_newer_version(latest, version) {
+                        let held_back = tool
+                            .held_back
+                            .as_ref()
+                            .map(|reason| format!(" (held back: {})", reason).bright_black())
+                            .unwrap_or_default();
                         format!(
-                            "{} → {} available",
+                            "{} → {}{} available{}",
                             version_str.yellow(),
-                            latest.bright_blue()
+                            latest.bright_blue(),
+                            channel,
+                            held_back
                         )
                     } else {
                         version_str.green().to_string()
@@ -229,17 +1045,28 @@ pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize,
                 version_str.green().to_string()
             }
         }
-        None => {
-            if check_latest && tool.latest.is_some() {
+        None if tool.detection_timed_out => "detection timed out".yellow().to_string(),
+        None => match &tool.latest {
+            Some(latest) if check_latest => {
                 format!(
-                    "{} ({})",
+                    "{} ({}{})",
                     "not installed".red(),
-                    tool.latest.as_ref().unwrap().bright_blue()
+                    latest.bright_blue(),
+                    channel
                 )
-            } else {
-                "not installed".red().to_string()
             }
-        }
+            _ => "not installed".red().to_string(),
+        },
+    };
+    let method = tool
+        .method
+        .map(|method| format!(" via {}", method).bright_black().to_string())
+        .unwrap_or_default();
+    let note = match &tool.latest_error {
+        Some(failure) if check_latest => format!(" (lookup failed: {})", failure.reason)
+            .yellow()
+            .to_string(),
+        _ => String::new(),
     };
 
     let name_padding = label_width.saturating_sub(tool.name.len());
@@ -249,19 +1076,35 @@ pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize,
     let id_spacer = " ".repeat(id_padding + 1);
 
     println!(
-        "{}{}{}{}{}",
+        "{}{}{}{}{}{}{}",
         format!("{}:", tool.name).bold(),
         name_spacer,
         identifier.bright_black(),
         id_spacer,
-        status
+        status,
+        note,
+        method
     );
 }
 
 #[cfg(test)]
 mod tests {
-    use super::fetch_npm_latest;
+    use super::{
+        EXIT_LOOKUP_FAILED, EXIT_MISSING, EXIT_OUTDATED, FailOn, HeldBack, LATEST_SOURCES,
+        LatestSource, Lookup, LookupError, LookupFailure, UpgradePolicy, check_exit_code,
+        fetch_text, held_back, lookup_claude, lookup_npm, lookup_script, npm_dist_tags,
+    };
+    use crate::{cache::Validators, tools::ToolVersion};
     use httpmock::prelude::*;
+    use reqwest::header::HeaderMap;
+    use std::time::Duration;
+
+    fn version(lookup: Result<Lookup, LookupError>) -> Option<String> {
+        match lookup {
+            Ok(Lookup::Found { version, .. }) => Some(version),
+            _ => None,
+        }
+    }
 
     #[tokio::test]
     async fn it_fetches_latest_from_npm_dist_tags() {
@@ -275,7 +1118,386 @@ mod tests {
             })
             .await;
 
-        let latest = fetch_npm_latest(&format!("{}/@github/copilot", server.base_url())).await;
+        let latest = version(
+            lookup_npm(
+                &format!("{}/@github/copilot", server.base_url()),
+                "latest",
+                None,
+            )
+            .await,
+        );
         assert_eq!(latest.as_deref(), Some("0.0.357"));
     }
+
+    #[tokio::test]
+    async fn it_reads_publish_time_from_npm() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/cline");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(
+                        r#"{"dist-tags":{"latest":"1.0.1"},
+                            "time":{"1.0.0":"2025-01-01T00:00:00.000Z","1.0.1":"2025-01-02T00:00:00.000Z"}}"#,
+                    );
+            })
+            .await;
+
+        match lookup_npm(&server.url("/cline"), "latest", None).await {
+            Ok(Lookup::Found { published_at, .. }) => assert_eq!(published_at, Some(1735776000)),
+            other => panic!("expected a fresh lookup, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_lists_every_npm_dist_tag() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/@anthropic-ai/claude-code");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"dist-tags":{"stable":"1.0.40","latest":"1.0.42","next":"1.0.43"}}"#);
+            })
+            .await;
+
+        let tags = npm_dist_tags(&server.url("/@anthropic-ai/claude-code"))
+            .await
+            .unwrap();
+        assert_eq!(
+            tags.into_iter().collect::<Vec<_>>(),
+            [
+                ("latest".to_string(), "1.0.42".to_string()),
+                ("next".to_string(), "1.0.43".to_string()),
+                ("stable".to_string(), "1.0.40".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_fetches_selected_channel_from_npm_dist_tags() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/@kilocode/cli");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"dist-tags":{"latest":"0.10.2","next":"0.11.0-rc.1"}}"#);
+            })
+            .await;
+
+        let url = format!("{}/@kilocode/cli", server.base_url());
+        assert_eq!(
+            version(lookup_npm(&url, "next", None).await).as_deref(),
+            Some("0.11.0-rc.1")
+        );
+        assert_eq!(version(lookup_npm(&url, "beta", None).await), None);
+    }
+
+    #[tokio::test]
+    async fn it_reads_claude_latest_from_release_manifest() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/claude-code-releases/latest");
+                then.status(200).body("1.0.44\n");
+            })
+            .await;
+
+        let latest = version(
+            lookup_claude(
+                &format!("{}/claude-code-releases/latest", server.base_url()),
+                &format!("{}/@anthropic-ai/claude-code", server.base_url()),
+                "latest",
+                None,
+                None,
+            )
+            .await,
+        );
+        assert_eq!(latest.as_deref(), Some("1.0.44"));
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_npm_when_claude_manifest_is_unavailable() {
+        let server = MockServer::start_async().await;
+        let _manifest = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/claude-code-releases/latest");
+                then.status(404).body("<Error>NoSuchKey</Error>");
+            })
+            .await;
+        let _npm = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/@anthropic-ai/claude-code");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"dist-tags":{"latest":"1.0.43"}}"#);
+            })
+            .await;
+
+        let lookup = lookup_claude(
+            &format!("{}/claude-code-releases/latest", server.base_url()),
+            &format!("{}/@anthropic-ai/claude-code", server.base_url()),
+            "latest",
+            None,
+            None,
+        )
+        .await;
+        let Ok(Lookup::Fallback { primary, lookup }) = lookup else {
+            panic!("expected the npm fallback, got {:?}", lookup);
+        };
+        assert!(matches!(primary, LookupError::Status { .. }));
+        assert_eq!(version(Ok(*lookup)).as_deref(), Some("1.0.43"));
+    }
+
+    #[tokio::test]
+    async fn it_keeps_the_manifest_error_when_the_npm_fallback_fails_too() {
+        let server = MockServer::start_async().await;
+        let _manifest = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/claude-code-releases/latest");
+                then.status(404).body("<Error>NoSuchKey</Error>");
+            })
+            .await;
+        let _npm = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/@anthropic-ai/claude-code");
+                then.status(502);
+            })
+            .await;
+
+        let error = lookup_claude(
+            &format!("{}/claude-code-releases/latest", server.base_url()),
+            &format!("{}/@anthropic-ai/claude-code", server.base_url()),
+            "latest",
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.reason(), "HTTP 502");
+        let chain = error.chain();
+        assert_eq!(chain.len(), 2);
+        assert!(chain[0].contains("/@anthropic-ai/claude-code returned 502"));
+        assert!(chain[1].starts_with("fell back after: "));
+        assert!(chain[1].contains("/claude-code-releases/latest returned 404"));
+    }
+
+    fn installer_script_source(name: &str) -> (&'static str, &'static str) {
+        LATEST_SOURCES
+            .iter()
+            .find_map(|(tool, source)| match source {
+                LatestSource::InstallerScript { url, pattern } if *tool == name => {
+                    Some((*url, *pattern))
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    async fn latest_from_script(name: &str, script: &'static str) -> Result<Lookup, LookupError> {
+        let (_, pattern) = installer_script_source(name);
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/install");
+                then.status(200).body(script);
+            })
+            .await;
+
+        lookup_script(&format!("{}/install", server.base_url()), pattern, None).await
+    }
+
+    #[tokio::test]
+    async fn it_extracts_cursor_version_from_install_script() {
+        let script = r#"#!/usr/bin/env bash
+set -euo pipefail
+DOWNLOAD_URL="https://downloads.cursor.com/lab/2025.10.22-f894c20/${OS}/${ARCH}/agent-cli-package.tar.gz"
+"#;
+        let latest = version(latest_from_script("Cursor CLI", script).await);
+        assert_eq!(latest.as_deref(), Some("2025.10.22-f894c20"));
+    }
+
+    #[tokio::test]
+    async fn it_extracts_factory_version_from_install_script() {
+        let script =
+            "#!/bin/sh\nset -e\n\n  VER=\"0.22.3\"\nBASE_URL=\"https://downloads.factory.ai\"\n";
+        let latest = version(latest_from_script("Factory CLI", script).await);
+        assert_eq!(latest.as_deref(), Some("0.22.3"));
+    }
+
+    #[tokio::test]
+    async fn it_reports_a_format_change_when_install_script_has_no_version() {
+        match latest_from_script("Factory CLI", "#!/bin/sh\necho hello\n").await {
+            Err(error @ LookupError::InvalidResponse { .. }) => {
+                assert_eq!(error.reason(), "unexpected response format");
+                assert!(error.chain()[0].contains("no version matching"));
+            }
+            other => panic!("expected an invalid response, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_reports_http_status_and_missing_channels() {
+        let server = MockServer::start_async().await;
+        let _missing = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/gone");
+                then.status(404);
+            })
+            .await;
+        let _tags = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/cline");
+                then.status(200).body(r#"{"dist-tags":{"latest":"1.0.1"}}"#);
+            })
+            .await;
+
+        let error = lookup_npm(&server.url("/gone"), "latest", None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.reason(), "HTTP 404");
+
+        let error = lookup_npm(&server.url("/cline"), "beta", None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.reason(), "no 'beta' release");
+    }
+
+    #[tokio::test]
+    async fn it_stores_validators_and_revalidates_with_them() {
+        let server = MockServer::start_async().await;
+        let _not_modified = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/cline")
+                    .header("if-none-match", "\"abc123\"");
+                then.status(304);
+            })
+            .await;
+        let _full = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/cline");
+                then.status(200)
+                    .header("etag", "\"abc123\"")
+                    .body(r#"{"dist-tags":{"latest":"1.0.1"}}"#);
+            })
+            .await;
+
+        let url = format!("{}/cline", server.base_url());
+        let validators = match lookup_npm(&url, "latest", None).await {
+            Ok(Lookup::Found {
+                version,
+                validators,
+                ..
+            }) => {
+                assert_eq!(version, "1.0.1");
+                validators
+            }
+            other => panic!("expected a fresh lookup, got {:?}", other),
+        };
+        assert_eq!(validators.etag.as_deref(), Some("\"abc123\""));
+
+        let revalidated = lookup_npm(&url, "latest", Some(&validators)).await;
+        assert!(matches!(revalidated, Ok(Lookup::NotModified)));
+
+        let stale = Validators {
+            etag: Some("\"old\"".to_string()),
+            last_modified: None,
+        };
+        assert_eq!(
+            version(lookup_npm(&url, "latest", Some(&stale)).await).as_deref(),
+            Some("1.0.1")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_reports_github_rate_limit_with_reset_time() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/repos/sst/opencode/releases");
+                then.status(403)
+                    .header("x-ratelimit-limit", "60")
+                    .header("x-ratelimit-remaining", "0")
+                    .header("x-ratelimit-reset", "1760000000")
+                    .body(r#"{"message":"API rate limit exceeded"}"#);
+            })
+            .await;
+
+        let url = format!("{}/repos/sst/opencode/releases", server.base_url());
+        match fetch_text(&url, HeaderMap::new()).await {
+            Err(LookupError::RateLimited { rate_limit, .. }) => {
+                assert_eq!(rate_limit.remaining, Some(0));
+                assert_eq!(rate_limit.resets_at, Some(1760000000));
+                assert!(
+                    rate_limit
+                        .describe()
+                        .starts_with("rate limited, resets at ")
+                );
+            }
+            other => panic!("expected a rate limit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_allows_upgrades_within_the_policy_level() {
+        assert!(UpgradePolicy::Patch.allows("1.2.3 (Claude Code)", "1.2.10"));
+        assert!(!UpgradePolicy::Patch.allows("1.2.3", "1.3.0"));
+        assert!(UpgradePolicy::Minor.allows("v1.2.3", "1.3.0"));
+        assert!(!UpgradePolicy::Minor.allows("1.2.3", "2.0.0"));
+        assert!(UpgradePolicy::All.allows("1.2.3", "2.0.0"));
+        assert!(!UpgradePolicy::Minor.allows("nightly", "1.0.0"));
+    }
+
+    #[test]
+    fn it_holds_back_updates_outside_the_policy_or_too_recent() {
+        let mut tool = ToolVersion::new("Cline CLI");
+        tool.installed = Some("1.2.3".to_string());
+        tool.latest = Some("2.0.0".to_string());
+        let day = Duration::from_secs(24 * 3600);
+
+        assert_eq!(
+            held_back(&tool, UpgradePolicy::Minor, Duration::ZERO),
+            Some(HeldBack::Policy(UpgradePolicy::Minor))
+        );
+        assert_eq!(held_back(&tool, UpgradePolicy::All, 3 * day), None);
+
+        tool.latest_published = Some(crate::cache::now() - 24 * 3600);
+        assert!(matches!(
+            held_back(&tool, UpgradePolicy::All, 3 * day),
+            Some(HeldBack::TooNew { .. })
+        ));
+        assert_eq!(held_back(&tool, UpgradePolicy::All, day / 2), None);
+    }
+
+    #[test]
+    fn it_picks_the_highest_exit_code_among_enabled_conditions() {
+        let mut outdated = ToolVersion::new("Amp").with_installed(Some("1.0.0".to_string()));
+        outdated.latest = Some("1.1.0".to_string());
+        let missing = ToolVersion::new("Codex CLI");
+        let mut unverified =
+            ToolVersion::new("Cline CLI").with_installed(Some("2.0.0".to_string()));
+        unverified.latest_error = Some(LookupFailure {
+            reason: "HTTP 500".to_string(),
+            chain: Vec::new(),
+        });
+
+        let tools = [outdated.clone(), missing.clone()];
+        assert_eq!(check_exit_code(&tools, false, &[]), EXIT_OUTDATED);
+        assert_eq!(check_exit_code(&tools, true, &[]), EXIT_MISSING);
+        assert_eq!(
+            check_exit_code(&tools, true, &[FailOn::Outdated]),
+            EXIT_OUTDATED
+        );
+        assert_eq!(check_exit_code(&[missing], true, &[FailOn::Outdated]), 0);
+        let tools = [outdated, unverified];
+        assert_eq!(check_exit_code(&tools, false, &[]), EXIT_LOOKUP_FAILED);
+        assert_eq!(
+            check_exit_code(&tools, false, &[FailOn::Outdated]),
+            EXIT_OUTDATED
+        );
+        assert_eq!(check_exit_code(&tools[1..], false, &[FailOn::Missing]), 0);
+    }
 }